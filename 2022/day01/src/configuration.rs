@@ -3,4 +3,8 @@ use std::io;
 
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub top: Option<usize>,
+    pub stats: bool,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
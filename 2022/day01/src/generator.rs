@@ -0,0 +1,21 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+/// `size` elves carrying 1 to 15 items each, the last one without a
+/// trailing blank line like the puzzle input.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for elf in 0..size {
+        if elf > 0 {
+            input.push('\n');
+        }
+        for _ in 0..rng.index(1..=15) {
+            writeln!(input, "{}", rng.between(1000..=60000)).unwrap();
+        }
+    }
+
+    input
+}
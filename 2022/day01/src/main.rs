@@ -1,4 +1,5 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::BufReader;
@@ -10,6 +11,11 @@ fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
     
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -25,15 +31,38 @@ fn main() {
 
 }
 
+/// Usage: `<input> [--top K] [--stats]`. `--top` lists the K elves carrying
+/// the most calories, `--stats` adds the mean and median totals.
+/// `--generate <seed> <size>` prints a random input of `size` elves instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason)
-        };
-        Configuration { input_file_buffer: Some(BufReader::new(input_file.unwrap())) }
-    } else { 
-        Configuration { input_file_buffer: None }
+    let mut config = Configuration {
+        input_file_buffer: None,
+        top: None,
+        stats: false,
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => match args.next().map(|k| k.parse::<usize>()) {
+                Some(Ok(k)) => config.top = Some(k),
+                _ => panic!("Expected an amount of elves after --top"),
+            },
+            "--stats" => config.stats = true,
+            "--generate" => match input_gen::parse_args(&mut args) {
+                Some(generate) => config.generate = Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            },
+            _ => {
+                let input_file = match File::open(&arg) {
+                    Ok(f) => f,
+                    Err(reason) => panic!("Couldn't open file {:?}: {}", arg, reason),
+                };
+                config.input_file_buffer = Some(BufReader::new(input_file));
+            }
+        }
     }
+
+    config
 }
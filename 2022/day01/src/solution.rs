@@ -1,35 +1,96 @@
-use std::io::BufRead;
-use std::collections::BinaryHeap;
+use std::fmt::Write;
+
 use crate::configuration::Configuration;
 
-pub fn run(config: Configuration) -> Result<String, String>{
-    let mut heap = BinaryHeap::new();
-
-    if config.input_file_buffer.is_some() {
-        let mut temp_total: i32 = 0;
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok(calories) = line {
-                if calories == "" {
-                    heap.push(temp_total);
-                    temp_total = 0;
-                } else {
-                    if let Ok(n) = calories.parse::<i32>() {
-                        temp_total += n;
-                    }
-                }
+use self::elf_inventory::ElfInventory;
+
+mod elf_inventory;
+
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let inventory = ElfInventory::parse(input_buf)?;
+
+        let mut result = format!(
+            "Part 1: {}, Part 2: {}",
+            inventory.top_n_total(1),
+            inventory.top_n_total(3)
+        );
+
+        if let Some(k) = config.top {
+            writeln!(result).unwrap();
+            for (rank, (index, total)) in inventory.top_n(k).into_iter().enumerate() {
+                let items = inventory.items(index).map_or(0, <[u64]>::len);
+                write!(
+                    result,
+                    "\n{:>4}. Elf {}: {} calories in {} items",
+                    rank + 1,
+                    index + 1,
+                    total,
+                    items
+                )
+                .unwrap();
             }
         }
-        
-        let mut ret: i32 = 0;
-        for _ in 1..=3 {
-            if let Some(x) = heap.pop() {
-                ret += x;
-            }
+
+        if config.stats {
+            write!(
+                result,
+                "\n\n{} elves, mean {:.1}, median {:.1}",
+                inventory.len(),
+                inventory.mean().unwrap_or(0.0),
+                inventory.median().unwrap_or(0.0)
+            )
+            .unwrap();
         }
 
-        return Ok(ret.to_string());
+        Ok(result)
     } else {
         Err("File was not opened!".to_string())
     }
-    
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::elf_inventory::ElfInventory;
+
+    #[test]
+    fn example() {
+        let inventory = ElfInventory::parse(include_str!("../test.txt").as_bytes()).unwrap();
+
+        // The last elf has no trailing blank line
+        assert_eq!(inventory.len(), 5);
+        assert_eq!(inventory.items(4), Some(&[10000][..]));
+        assert_eq!(inventory.top_n_total(1), 24000);
+        assert_eq!(inventory.top_n_total(3), 45000);
+        assert_eq!(inventory.top_n(2), vec![(3, 24000), (2, 11000)]);
+        assert_eq!(inventory.mean(), Some(11000.0));
+        assert_eq!(inventory.median(), Some(10000.0));
+    }
+
+    #[test]
+    fn blank_lines_and_ties() {
+        let inventory = ElfInventory::parse("\n5\n\n\n\n3\n2\n\n1\n\n".as_bytes()).unwrap();
+
+        assert_eq!(inventory.len(), 3);
+        assert_eq!(inventory.top_n(5), vec![(0, 5), (1, 5), (2, 1)]);
+        assert_eq!(inventory.median(), Some(5.0));
+        assert!(ElfInventory::parse("1\nx".as_bytes()).is_err());
+        assert_eq!(ElfInventory::parse("".as_bytes()).unwrap().mean(), None);
+    }
+
+    #[test]
+    fn generated_inputs() {
+        for seed in 0..20 {
+            let input = generate(seed, 500);
+            let inventory = ElfInventory::parse(input.as_bytes()).unwrap();
+            let mut totals = (0..inventory.len())
+                .map(|ix| inventory.items(ix).unwrap().iter().sum::<u64>())
+                .collect::<Vec<_>>();
+            totals.sort_unstable_by(|a, b| b.cmp(a));
+
+            assert_eq!(inventory.len(), 500);
+            assert_eq!(inventory.top_n_total(3), totals[..3].iter().sum::<u64>());
+        }
+    }
 }
@@ -0,0 +1,102 @@
+use std::{cmp::Reverse, collections::BinaryHeap, io::BufRead};
+
+/// Calories of the food items carried by every elf, in input order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfInventory {
+    items: Vec<Vec<u64>>,
+    totals: Vec<u64>,
+}
+
+impl ElfInventory {
+    /// Reads one item per line, elves being separated by one or more blank
+    /// lines. The last elf doesn't need a trailing blank line.
+    pub fn parse(buf: impl BufRead) -> Result<Self, String> {
+        let mut items = vec![];
+        let mut current: Vec<u64> = vec![];
+
+        for (ix, line) in buf.lines().enumerate() {
+            let line = line.map_err(|e| format!("Couldn't read line: {}", e))?;
+            let line = line.trim();
+
+            if line.is_empty() {
+                if !current.is_empty() {
+                    items.push(std::mem::take(&mut current));
+                }
+            } else {
+                current.push(
+                    line.parse::<u64>().map_err(|e| {
+                        format!("Line {}: invalid calories {:?}: {}", ix + 1, line, e)
+                    })?,
+                );
+            }
+        }
+        if !current.is_empty() {
+            items.push(current);
+        }
+
+        Ok(Self::new(items))
+    }
+
+    pub fn new(items: Vec<Vec<u64>>) -> Self {
+        let totals = items.iter().map(|elf| elf.iter().sum()).collect();
+        Self { items, totals }
+    }
+
+    pub fn len(&self) -> usize {
+        self.totals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.totals.is_empty()
+    }
+
+    /// Items carried by the elf at `index`, counting from 0 in input order.
+    pub fn items(&self, index: usize) -> Option<&[u64]> {
+        self.items.get(index).map(Vec::as_slice)
+    }
+
+    /// Indices and totals of the `k` elves carrying the most calories, most
+    /// first. Elves carrying the same amount keep their input order.
+    pub fn top_n(&self, k: usize) -> Vec<(usize, u64)> {
+        // Min-heap of the best k seen so far, the weakest on top
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for (index, &total) in self.totals.iter().enumerate() {
+            heap.push(Reverse((total, Reverse(index))));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((total, Reverse(index)))| (index, total))
+            .collect()
+    }
+
+    /// Sum of the calories carried by the top `k` elves.
+    pub fn top_n_total(&self, k: usize) -> u64 {
+        self.top_n(k).iter().map(|&(_, total)| total).sum()
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.totals.iter().sum::<u64>() as f64 / self.len() as f64)
+        }
+    }
+
+    /// Middle total, or the mean of the two middle ones for an even amount
+    /// of elves.
+    pub fn median(&self) -> Option<f64> {
+        let mut sorted = self.totals.clone();
+        sorted.sort_unstable();
+
+        let middle = sorted.len() / 2;
+        match sorted.len() {
+            0 => None,
+            n if n % 2 == 1 => Some(sorted[middle] as f64),
+            _ => Some((sorted[middle - 1] + sorted[middle]) as f64 / 2.0),
+        }
+    }
+}
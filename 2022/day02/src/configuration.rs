@@ -1,6 +1,12 @@
 use std::fs::File;
 use std::io;
 
+pub use parts::Part;
+
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub part: Part,
+    pub rules: Option<String>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
@@ -0,0 +1,17 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+/// `size` rounds of the classic game, `A`-`C` against `X`-`Z`.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let theirs = rng.pick(&['A', 'B', 'C']);
+        let mine = rng.pick(&['X', 'Y', 'Z']);
+        writeln!(input, "{} {}", theirs, mine).unwrap();
+    }
+
+    input
+}
@@ -1,105 +1,135 @@
-use std::io::BufRead;
+use std::{fs, io::BufRead};
 use crate::configuration::Configuration;
 
-pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        let mut total_points: i32 = 0;
+use self::{
+    rules::Rules,
+    strategy::{Interpretation, StrategyGuide},
+};
 
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok((left, right)) = split_round(line.unwrap()) {
-                //total_points += play_pt1(left, right);
-                total_points += play_pt2(left, right);
-            }
-        }
+mod rules;
+mod strategy;
 
-        Ok(total_points.to_string())
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let rules = load_rules(config.rules.as_deref())?;
+        let lines = input_buf
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| format!("Couldn't read line: {}", e))?;
+        let guide = StrategyGuide::parse(lines.iter().map(String::as_str))?;
+
+        config.part.try_report(
+            || Ok(guide.score(&rules, Interpretation::MyShape)?.to_string()),
+            || {
+                Ok(guide
+                    .score(&rules, Interpretation::DesiredOutcome)?
+                    .to_string())
+            },
+        )
     } else {
         panic!("A file was not provided");
     }
 }
 
-fn split_round(str: String) -> Result<(char, char), String> {
-    let items: Vec<&str> = str.split_whitespace().collect::<Vec<_>>();
-
-    if items.len() == 2 {
-        let left = items.get(0);
-        let right = items.get(1);
-
-        if left.is_some() && right.is_some() {
-            let c_left: char = left.unwrap().to_string().chars().nth(0) .unwrap();
-            let c_right: char = right.unwrap().to_string().chars().nth(0).unwrap();
-
-
-            return Ok((c_left, c_right));
-        };
+/// `classic` (the default), `lizard-spock` or the path of a dominance table.
+fn load_rules(rules: Option<&str>) -> Result<Rules, String> {
+    match rules {
+        None | Some("classic") => Ok(Rules::classic()),
+        Some("lizard-spock") => Ok(Rules::lizard_spock()),
+        Some(path) => {
+            let table = fs::read_to_string(path)
+                .map_err(|e| format!("Couldn't read rules {}: {}", path, e))?;
+            Rules::from_table(&table)
+        }
     }
-
-
-    let error: String = format!("Could not split string: {}", str);
-    Err(error)
 }
 
-// PART 1
-#[allow(dead_code)]
-fn play_pt1(oponent: char, player: char) -> i32 {
-    let defeater: char = get_defeater(&oponent);
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{
+        rules::{Outcome, Rules, Shape},
+        strategy::{Interpretation, StrategyGuide},
+    };
 
-    let outcome = if player == defeater { 6 } else if player == get_equivalent(&oponent) { 3 } else { 0 };
+    #[test]
+    fn example() {
+        let guide = StrategyGuide::parse(include_str!("../test.txt").lines()).unwrap();
+        let rules = Rules::classic();
 
-    outcome + get_move_points(&player)
-}
+        assert_eq!(guide.score(&rules, Interpretation::MyShape), Ok(15));
+        assert_eq!(guide.score(&rules, Interpretation::DesiredOutcome), Ok(12));
+    }
 
-/* PART 2 
-outcome: X = Lose, Y = Draw, Z = Win
-*/
-fn play_pt2(oponent: char, outcome: char) -> i32 {
-    let points: i32 = match outcome {
-        'X' => get_move_points(&get_loser(&oponent)),
-        'Y' => 3 + get_move_points(&oponent),
-        'Z' => 6 + get_move_points(&get_defeater(&oponent)),
-        _ => panic!("Uknown parameter"),
-    };
+    #[test]
+    fn lizard_spock() {
+        let rules = Rules::lizard_spock();
+        let table = Rules::from_table(
+            "Rock Paper Scissors Spock Lizard
+            Scissors beats Paper Lizard
+            Paper beats Rock Spock
+            Rock beats Lizard Scissors
+            Lizard beats Spock Paper
+            Spock beats Scissors Rock",
+        )
+        .unwrap();
+
+        for mine in (0..5).map(Shape) {
+            for theirs in (0..5).map(Shape) {
+                assert_eq!(rules.outcome(mine, theirs), table.outcome(mine, theirs));
+            }
+        }
 
-    points
-}
+        let spock = rules.find("spock").unwrap();
+        let lizard = rules.find("Lizard").unwrap();
+        assert_eq!(rules.outcome(lizard, spock), Outcome::Win);
 
-fn get_move_points(play: &char) -> i32 {
-    match play {
-       'A' => 1,
-       'B' => 2,
-       'C' => 3,
-       'X' => 1,
-       'Y' => 2,
-       'Z' => 3,
-       _ => panic!("Uknown parameter"),
+        let guide = StrategyGuide::parse(["D Z", "E Lizard", "Spock X"]).unwrap();
+        assert_eq!(guide.score(&rules, Interpretation::MyShape), Ok(11 + 8 + 3));
     }
-}
 
-fn get_defeater(play: &char) -> char {
-    match play {
-        'A' => 'Y',
-        'B' => 'Z',
-        'C' => 'X',
-        _ => panic!("Uknown parameter"),
+    #[test]
+    fn invalid_tables_and_symbols() {
+        assert!(Rules::from_table("Rock Paper\nRock beats Paper\nPaper beats Rock").is_err());
+        assert!(Rules::from_table("Rock Paper Scissors\nPaper beats Rock").is_err());
+        assert!(Rules::cyclic(&["Rock", "Paper"]).is_err());
+
+        let guide = StrategyGuide::parse(["A Q"]).unwrap();
+        assert_eq!(
+            guide.score(&Rules::classic(), Interpretation::MyShape),
+            Err("Line 1: unknown shape \"Q\"".to_string())
+        );
     }
-}
 
-fn get_loser(play: &char) -> char  {
-    match play {
-        'A' => 'Z',
-        'B' => 'X',
-        'C' => 'Y',
-        _ => panic!("Uknown parameter"),
-    }
-}
+    #[test]
+    fn generated_guides() {
+        // Shapes and outcomes both cycle with period 3, so the scores follow
+        // from modular arithmetic
+        let score = |line: &str, desired_outcome: bool| {
+            let bytes = line.as_bytes();
+            let theirs = (bytes[0] - b'A') as u32;
+            let right = (bytes[2] - b'X') as u32;
+            let (mine, outcome) = if desired_outcome {
+                ((theirs + right + 2) % 3, right)
+            } else {
+                (right, (right + 4 - theirs) % 3)
+            };
+            mine + 1 + outcome * 3
+        };
 
-#[allow(dead_code)]
-fn get_equivalent(play: &char) -> char {
-    match play {
-        'A' => 'X',
-        'B' => 'Y',
-        'C' => 'Z',
-        _ => panic!("Uknown parameter"),
+        for seed in 0..20 {
+            let input = generate(seed, 1000);
+            let guide = StrategyGuide::parse(input.lines()).unwrap();
+            let rules = Rules::classic();
+
+            assert_eq!(
+                guide.score(&rules, Interpretation::MyShape),
+                Ok(input.lines().map(|line| score(line, false)).sum())
+            );
+            assert_eq!(
+                guide.score(&rules, Interpretation::DesiredOutcome),
+                Ok(input.lines().map(|line| score(line, true)).sum())
+            );
+        }
     }
-
 }
@@ -0,0 +1,171 @@
+use std::fmt;
+
+/// Index of a shape in its `Rules`. Shapes score their index + 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shape(pub usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub fn points(self) -> u32 {
+        match self {
+            Outcome::Lose => 0,
+            Outcome::Draw => 3,
+            Outcome::Win => 6,
+        }
+    }
+}
+
+/// Shapes of a rock-paper-scissors style game and which shape defeats
+/// which. Every pair of distinct shapes must have exactly one winner.
+pub struct Rules {
+    names: Vec<String>,
+    beats: Vec<Vec<bool>>,
+}
+
+impl Rules {
+    pub fn classic() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors"]).unwrap()
+    }
+
+    /// Rock-Paper-Scissors-Lizard-Spock.
+    pub fn lizard_spock() -> Self {
+        Self::cyclic(&["Rock", "Paper", "Scissors", "Spock", "Lizard"]).unwrap()
+    }
+
+    /// Balanced game on an odd number of shapes: every shape defeats the
+    /// shapes an odd number of places before it, wrapping around. With three
+    /// shapes that is the classic game.
+    pub fn cyclic(names: &[&str]) -> Result<Self, String> {
+        let n = names.len();
+        if n.is_multiple_of(2) {
+            return Err(format!(
+                "A cyclic game needs an odd number of shapes, got {}",
+                n
+            ));
+        }
+
+        let beats = (0..n)
+            .map(|winner| {
+                (0..n)
+                    .map(|loser| (winner + n - loser) % n % 2 == 1)
+                    .collect()
+            })
+            .collect();
+
+        Self::new(names.iter().map(|name| name.to_string()).collect(), beats)
+    }
+
+    /// Parses a dominance table. The first line lists the shapes in scoring
+    /// order, every other line is `<shape> beats <shape> [<shape> ...]`.
+    pub fn from_table(table: &str) -> Result<Self, String> {
+        let mut lines = table.lines().filter(|line| !line.trim().is_empty());
+        let names: Vec<String> = lines
+            .next()
+            .ok_or("Empty dominance table")?
+            .split_whitespace()
+            .map(str::to_string)
+            .collect();
+        let index_of = |name: &str| {
+            names
+                .iter()
+                .position(|shape| shape == name)
+                .ok_or_else(|| format!("Unknown shape in dominance table: {}", name))
+        };
+
+        let mut beats = vec![vec![false; names.len()]; names.len()];
+        for line in lines {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [winner, "beats", losers @ ..] if !losers.is_empty() => {
+                    let winner = index_of(winner)?;
+                    for loser in losers {
+                        beats[winner][index_of(loser)?] = true;
+                    }
+                }
+                _ => {
+                    return Err(format!(
+                        "Expected \"<shape> beats <shape>\", got {:?}",
+                        line
+                    ))
+                }
+            }
+        }
+
+        Self::new(names, beats)
+    }
+
+    fn new(names: Vec<String>, beats: Vec<Vec<bool>>) -> Result<Self, String> {
+        if names.is_empty() {
+            return Err("A game needs at least one shape".to_string());
+        }
+
+        for a in 0..names.len() {
+            if beats[a][a] {
+                return Err(format!("{} can't beat itself", names[a]));
+            }
+            for b in a + 1..names.len() {
+                if beats[a][b] == beats[b][a] {
+                    return Err(format!(
+                        "Exactly one of {} and {} has to win",
+                        names[a], names[b]
+                    ));
+                }
+            }
+        }
+
+        Ok(Self { names, beats })
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.names[shape.0]
+    }
+
+    pub fn find(&self, name: &str) -> Option<Shape> {
+        self.names
+            .iter()
+            .position(|shape| shape.eq_ignore_ascii_case(name))
+            .map(Shape)
+    }
+
+    pub fn shape_points(&self, shape: Shape) -> u32 {
+        shape.0 as u32 + 1
+    }
+
+    /// Outcome of a round for the player playing `mine`.
+    pub fn outcome(&self, mine: Shape, theirs: Shape) -> Outcome {
+        if mine == theirs {
+            Outcome::Draw
+        } else if self.beats[mine.0][theirs.0] {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    /// The lowest scoring shape that gets `outcome` against `theirs`.
+    pub fn shape_for(&self, theirs: Shape, outcome: Outcome) -> Option<Shape> {
+        (0..self.len())
+            .map(Shape)
+            .find(|&mine| self.outcome(mine, theirs) == outcome)
+    }
+
+    /// Points of a round: the shape played plus the outcome.
+    pub fn score(&self, mine: Shape, theirs: Shape) -> u32 {
+        self.shape_points(mine) + self.outcome(mine, theirs).points()
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.names.join("-"))
+    }
+}
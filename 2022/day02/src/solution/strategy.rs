@@ -0,0 +1,96 @@
+use super::rules::{Outcome, Rules, Shape};
+
+/// Meaning of the second column of the strategy guide.
+#[derive(Clone, Copy)]
+pub enum Interpretation {
+    /// The shape to play.
+    MyShape,
+    /// `X` to lose, `Y` to draw and `Z` to win.
+    DesiredOutcome,
+}
+
+/// Unresolved columns of a strategy guide, interpreted against some rules.
+pub struct StrategyGuide {
+    rounds: Vec<(usize, String, String)>,
+}
+
+impl StrategyGuide {
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut rounds = vec![];
+
+        for (ix, line) in lines.into_iter().enumerate() {
+            match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [] => {}
+                [left, right] => rounds.push((ix + 1, left.to_string(), right.to_string())),
+                _ => return Err(format!("Line {}: could not split {:?}", ix + 1, line)),
+            }
+        }
+
+        Ok(Self { rounds })
+    }
+
+    /// Total score of following the guide.
+    pub fn score(&self, rules: &Rules, interpretation: Interpretation) -> Result<u32, String> {
+        let mut total = 0;
+
+        for (line, left, right) in self.rounds.iter() {
+            let theirs = opponent_shape(rules, left)
+                .ok_or_else(|| format!("Line {}: unknown shape {:?}", line, left))?;
+            let mine = match interpretation {
+                Interpretation::MyShape => player_shape(rules, right)
+                    .ok_or_else(|| format!("Line {}: unknown shape {:?}", line, right))?,
+                Interpretation::DesiredOutcome => {
+                    let outcome = outcome(right)
+                        .ok_or_else(|| format!("Line {}: unknown outcome {:?}", line, right))?;
+                    rules.shape_for(theirs, outcome).ok_or_else(|| {
+                        format!(
+                            "Line {}: no shape gets {:?} against {}",
+                            line,
+                            outcome,
+                            rules.name(theirs)
+                        )
+                    })?
+                }
+            };
+
+            total += rules.score(mine, theirs);
+        }
+
+        Ok(total)
+    }
+}
+
+/// Opponent shapes are written `A`, `B`, `C`, ... in scoring order, or by
+/// name.
+fn opponent_shape(rules: &Rules, symbol: &str) -> Option<Shape> {
+    letter_index(symbol, b'A')
+        .filter(|&ix| ix < rules.len())
+        .map(Shape)
+        .or_else(|| rules.find(symbol))
+}
+
+/// Player shapes use the last letters of the alphabet in scoring order
+/// (`X`, `Y`, `Z` for three shapes), or their name.
+fn player_shape(rules: &Rules, symbol: &str) -> Option<Shape> {
+    let first = (b'Z' + 1).checked_sub(rules.len() as u8)?;
+    letter_index(symbol, first)
+        .filter(|&ix| ix < rules.len())
+        .map(Shape)
+        .or_else(|| rules.find(symbol))
+}
+
+fn outcome(symbol: &str) -> Option<Outcome> {
+    match symbol {
+        "X" => Some(Outcome::Lose),
+        "Y" => Some(Outcome::Draw),
+        "Z" => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+fn letter_index(symbol: &str, first: u8) -> Option<usize> {
+    match symbol.as_bytes() {
+        &[c] if c >= first && c.is_ascii_uppercase() => Some((c - first) as usize),
+        _ => None,
+    }
+}
@@ -0,0 +1,47 @@
+use input_gen::Rng;
+
+const ITEM_TYPES: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// `size` groups of three rucksacks. Every rucksack has exactly one item
+/// type in both compartments and every group exactly one badge: apart from
+/// the badge, the rucksacks of a group draw from disjoint item types, and
+/// the two compartments of a rucksack only share one of them.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut types = ITEM_TYPES.to_vec();
+        rng.shuffle(&mut types);
+        let (badge, pools) = (types[0], &types[1..]);
+
+        for pool in pools.chunks(pools.len() / 3) {
+            let (shared, rest) = (pool[0], &pool[1..]);
+            let (left_pool, right_pool) = rest.split_at(rest.len() / 2);
+            let length = rng.index(2..=16);
+
+            let mut left = (0..length)
+                .map(|_| *rng.pick(left_pool))
+                .collect::<Vec<_>>();
+            let mut right = (0..length)
+                .map(|_| *rng.pick(right_pool))
+                .collect::<Vec<_>>();
+            left[rng.index(0..=length - 1)] = shared;
+            right[rng.index(0..=length - 1)] = shared;
+            // Keep the shared item when the badge lands next to it
+            let compartment = if rng.chance(0.5) {
+                &mut left
+            } else {
+                &mut right
+            };
+            let ix = compartment.iter().position(|&item| item != shared).unwrap();
+            compartment[ix] = badge;
+
+            input.push_str(std::str::from_utf8(&left).unwrap());
+            input.push_str(std::str::from_utf8(&right).unwrap());
+            input.push('\n');
+        }
+    }
+
+    input
+}
@@ -1,44 +1,36 @@
 use crate::configuration::Configuration;
-use std::{collections::HashSet, io::BufRead};
+use std::io::BufRead;
 
-pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        /* Part 1
-        let mut total_priority: i32 = 0;
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok(s) = line {
-                let errors = find_errors(s);
-
-                errors.iter().for_each(|item| total_priority += find_priority(item));
-        } else if let Err(e) = line {
-                return Err(format!("Couldn't read line: {}", e));
-            }
-        }
-        */
+use self::item_set::ItemSet;
 
-        /* Part 2
-         */
-        let mut iterator = config
-            .input_file_buffer
-            .unwrap()
+mod item_set;
+
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let rucksacks = input_buf
             .lines()
-            .map(|l| l.expect("Couldn't read line"))
-            .peekable();
-        let mut total_priority: i32 = 0;
-        loop {
-            if iterator.peek().is_none() {
-                break;
-            }
-            total_priority += find_priority(&find_badge_item_type(
-                iterator
-                    .next()
-                    .unwrap(),
-                iterator.next(),
-                iterator.next(),
-            ));
-        }
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| format!("Couldn't read line: {}", e))?;
 
-        return Ok(total_priority.to_string());
+        config.part.try_report(
+            || {
+                let mut total_priority = 0;
+                for (ix, rucksack) in rucksacks.iter().enumerate() {
+                    total_priority += find_errors(rucksack)
+                        .map_err(|e| format!("Line {}: {}", ix + 1, e))?
+                        .priority_sum();
+                }
+                Ok(total_priority.to_string())
+            },
+            || {
+                let mut total_priority = 0;
+                for (ix, group) in rucksacks.chunks(config.group_size).enumerate() {
+                    total_priority += find_badge_item_type(group, config.group_size)
+                        .map_err(|e| format!("Group {}: {}", ix + 1, e))?;
+                }
+                Ok(total_priority.to_string())
+            },
+        )
     } else {
         panic!("A file was not provided");
     }
@@ -47,56 +39,118 @@ pub fn run(config: Configuration) -> Result<String, String> {
 /*
 Part 1
 */
-#[allow(dead_code)]
-pub fn find_errors(line: String) -> HashSet<u8> {
-    let mut errors: HashSet<u8> = HashSet::new();
-    let rucksack: &[u8] = line.as_bytes();
-    let rucksack_size = rucksack.len();
-    let compartment_size = rucksack_size / 2;
-
-    for i in 0..compartment_size {
-        for k in compartment_size..rucksack_size {
-            if *rucksack.get(i).expect("Invalid item") == *rucksack.get(k).expect("Invalid item") {
-                errors.insert(rucksack.get(i).unwrap().clone());
-            }
-        }
+/// Item types found in both compartments of a rucksack.
+pub fn find_errors(rucksack: &str) -> Result<ItemSet, String> {
+    let rucksack = rucksack.as_bytes();
+    if !rucksack.len().is_multiple_of(2) {
+        return Err(format!(
+            "Can't split {} items into two compartments",
+            rucksack.len()
+        ));
     }
 
-    errors
-}
+    let (first, second) = rucksack.split_at(rucksack.len() / 2);
 
-pub fn find_badge_item_type(line1: String, line2: Option<String>, line3: Option<String>) -> u8 {
-    let mut badge: u8 = 0u8;
-    let rucksack1: &[u8] = line1.as_bytes();
-    //let rucksack2: &[u8] = line2.as_bytes();
-    //let rucksack3: &[u8] = line3.as_bytes();
-
-    for item in rucksack1.iter() {
-        if (line2.is_none() || line2.as_ref().unwrap().as_bytes().contains(item))
-            && (line3.is_none() || line3.as_ref().unwrap().as_bytes().contains(item))
-        {
-            badge = *item;
-            break;
-        }
-    }
-
-    badge
+    Ok(ItemSet::from_items(first)? & ItemSet::from_items(second)?)
 }
 
 /*
-    Lowercase item types a through z have priorities 1 through 26.
-        ASCII 97 - 122
-    Uppercase item types A through Z have priorities 27 through 52.
-        ASCII 65 - 90
+Part 2
 */
-pub fn find_priority(item: &u8) -> i32 {
-    let prio: u8 = if *item > 90u8 {
-        //lowercase
-        *item - 96
+/// Priority of the item type carried by every rucksack of the group. With
+/// small groups several types can be shared, their priorities are added up.
+pub fn find_badge_item_type(group: &[String], group_size: usize) -> Result<u32, String> {
+    if group.len() != group_size {
+        return Err(format!(
+            "Expected {} rucksacks, got {}",
+            group_size,
+            group.len()
+        ));
+    }
+
+    let mut common = ItemSet::from_items(group[0].as_bytes())?;
+    for rucksack in &group[1..] {
+        common = common & ItemSet::from_items(rucksack.as_bytes())?;
+    }
+
+    if common.is_empty() {
+        Err("No item type is shared by the whole group".to_string())
     } else {
-        //uppercase
-        *item - 38
+        Ok(common.priority_sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{
+        find_badge_item_type, find_errors,
+        item_set::{priority, ItemSet},
     };
 
-    prio as i32
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn example() {
+        let rucksacks: Vec<String> = EXAMPLE.lines().map(str::to_string).collect();
+
+        let errors: u32 = rucksacks
+            .iter()
+            .map(|rucksack| find_errors(rucksack).unwrap().priority_sum())
+            .sum();
+        let badges: u32 = rucksacks
+            .chunks(3)
+            .map(|group| find_badge_item_type(group, 3).unwrap())
+            .sum();
+
+        assert_eq!(errors, 157);
+        assert_eq!(badges, 70);
+    }
+
+    #[test]
+    fn groups_of_any_size() {
+        let rucksacks: Vec<String> = EXAMPLE.lines().map(str::to_string).collect();
+
+        let first_pair = ItemSet::from_items(rucksacks[0].as_bytes()).unwrap()
+            & ItemSet::from_items(rucksacks[1].as_bytes()).unwrap();
+        assert_eq!(first_pair.priorities().count(), 5);
+        assert_eq!(
+            find_badge_item_type(&rucksacks[..2], 2),
+            Ok(first_pair.priority_sum())
+        );
+        assert_eq!(
+            find_badge_item_type(&rucksacks[..3], 3),
+            Ok(priority(b'r').unwrap())
+        );
+        assert!(find_badge_item_type(&rucksacks[..2], 3).is_err());
+        assert!(find_badge_item_type(&rucksacks, 6).is_err());
+    }
+
+    #[test]
+    fn set_operations_and_invalid_items() {
+        let a = ItemSet::from_items(b"abcZ").unwrap();
+        let b = ItemSet::from_items(b"cZz").unwrap();
+
+        assert_eq!((a & b).priorities().collect::<Vec<_>>(), vec![3, 52]);
+        assert_eq!((a | b).priorities().count(), 5);
+        assert!(ItemSet::from_items(b"ab1").is_err());
+        assert!(find_errors("abc").is_err());
+    }
+
+    #[test]
+    fn generated_rucksacks() {
+        for seed in 0..20 {
+            let input = generate(seed, 200);
+            let rucksacks: Vec<String> = input.lines().map(str::to_string).collect();
+
+            assert_eq!(rucksacks.len(), 600);
+            for rucksack in &rucksacks {
+                assert_eq!(find_errors(rucksack).unwrap().priorities().count(), 1);
+            }
+            for group in rucksacks.chunks(3) {
+                let badge = find_badge_item_type(group, 3).unwrap();
+                assert!((1..=52).contains(&badge));
+            }
+        }
+    }
 }
@@ -0,0 +1,59 @@
+use std::ops::{BitAnd, BitOr};
+
+/// Set of item types, one bit per type. Bit `priority - 1` is set for an
+/// item type, so `a`..`z` use bits 0 to 25 and `A`..`Z` bits 26 to 51.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn from_items(items: &[u8]) -> Result<Self, String> {
+        items.iter().try_fold(ItemSet::default(), |set, &item| {
+            Ok(ItemSet(set.0 | 1 << (priority(item)? - 1)))
+        })
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Sum of the priorities of every item type in the set.
+    pub fn priority_sum(self) -> u32 {
+        self.priorities().sum()
+    }
+
+    pub fn priorities(self) -> impl Iterator<Item = u32> {
+        (0..52)
+            .filter(move |bit| self.0 & 1 << bit != 0)
+            .map(|bit| bit + 1)
+    }
+}
+
+/// Intersection
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+}
+
+/// Union
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+}
+
+/*
+    Lowercase item types a through z have priorities 1 through 26.
+    Uppercase item types A through Z have priorities 27 through 52.
+*/
+pub fn priority(item: u8) -> Result<u32, String> {
+    match item {
+        b'a'..=b'z' => Ok((item - b'a') as u32 + 1),
+        b'A'..=b'Z' => Ok((item - b'A') as u32 + 27),
+        _ => Err(format!("Invalid item type: {:?}", item as char)),
+    }
+}
@@ -0,0 +1,32 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+/// `size` pairs of section ranges between 1 and 99. A third of the second
+/// ranges are drawn inside the first one, so every overlap class shows up.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let (start, end) = range(&mut rng, 1, 99);
+        let (other_start, other_end) = if rng.chance(1.0 / 3.0) {
+            range(&mut rng, start, end)
+        } else {
+            range(&mut rng, 1, 99)
+        };
+
+        if rng.chance(0.5) {
+            writeln!(input, "{}-{},{}-{}", start, end, other_start, other_end).unwrap();
+        } else {
+            writeln!(input, "{}-{},{}-{}", other_start, other_end, start, end).unwrap();
+        }
+    }
+
+    input
+}
+
+fn range(rng: &mut Rng, low: u64, high: u64) -> (u64, u64) {
+    let start = rng.between(low..=high);
+    (start, rng.between(start..=high))
+}
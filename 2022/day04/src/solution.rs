@@ -1,62 +1,150 @@
-use std::io::BufRead;
+use std::{collections::BTreeMap, io::BufRead};
 use crate::configuration::Configuration;
 
-pub fn run(config: Configuration) -> Result<String, String>{
-
-    if config.input_file_buffer.is_some() {
-        let mut count: i32 = 0;
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok(s) = line { 
-                // Part 1 
-                // if is_one_fully_contained(get_assignments(s)) { count += 1; }
-                // Part 2
-                if are_overlapping(get_assignments(s)) { count += 1 }
-             }
+use self::section_range::{parse_pair, Classification};
+
+mod section_range;
+
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let mut classifications = vec![];
+        for (ix, line) in input_buf.lines().enumerate() {
+            let line = line.map_err(|e| format!("Couldn't read line: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let (left, right) = parse_pair(&line).map_err(|e| format!("Line {}: {}", ix + 1, e))?;
+            classifications.push(Classification::of(&left, &right));
+        }
+
+        let mut result = config.part.report(
+            || {
+                classifications
+                    .iter()
+                    .filter(|class| class.is_fully_contained())
+                    .count()
+                    .to_string()
+            },
+            || {
+                classifications
+                    .iter()
+                    .filter(|class| class.is_overlapping())
+                    .count()
+                    .to_string()
+            },
+        );
+        if config.classes {
+            for (class, count) in count_classes(&classifications) {
+                result.push_str(&format!("\n{:?}: {}", class, count));
+            }
         }
 
-        Ok(count.to_string())
+        Ok(result)
     } else {
         panic!("A file was not provided");
     }
-    
 }
 
-/* Part 1 */
-#[allow(dead_code)]
-pub fn is_one_fully_contained(a: ((u8, u8), (u8, u8))) -> bool {
-    if a.0.0 == a.1.0 || a.0.1 == a.1.1 { 
-        true 
-    } else if a.0.0 > a.1.0 && a.0.1 <= a.1.1 {
-        true
-    } else if a.0.0 < a.1.0 && a.0.1 >= a.1.1 {
-        true
-    } else {
-        false
+fn count_classes(classifications: &[Classification]) -> BTreeMap<Classification, usize> {
+    let mut counts = BTreeMap::new();
+    for &class in classifications {
+        *counts.entry(class).or_insert(0) += 1;
     }
+
+    counts
 }
 
-pub fn are_overlapping(a: ((u8, u8), (u8, u8))) -> bool {
-    if a.0.1 >= a.1.0 && a.0.0 <= a.1.1 {
-        true
-    } else if a.0.0 >= a.1.0 && a.0.0 <= a.1.1 {
-        true
-    } else {
-        false
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::generator::generate;
+    use crate::solution::{
+        count_classes,
+        section_range::{parse_pair, Classification, SectionRange},
+    };
+
+    #[test]
+    fn example() {
+        let classifications: Vec<Classification> = include_str!("../test.txt")
+            .lines()
+            .map(|line| {
+                let (left, right) = parse_pair(line).unwrap();
+                Classification::of(&left, &right)
+            })
+            .collect();
+        let counts = count_classes(&classifications);
+
+        assert_eq!(counts[&Classification::Disjoint], 2);
+        assert_eq!(counts[&Classification::Overlapping], 2);
+        assert_eq!(counts[&Classification::LeftContainsRight], 1);
+        assert_eq!(counts[&Classification::RightContainsLeft], 1);
+        assert_eq!(counts.get(&Classification::Equal), None);
+    }
+
+    #[test]
+    fn large_ids_and_invalid_ranges() {
+        let (left, right) = parse_pair("1000-4000000000,256-300").unwrap();
+
+        assert_eq!(Classification::of(&left, &right), Classification::Disjoint);
+        assert!(parse_pair("5-3,1-2").is_err());
+        assert!(parse_pair("1-2").is_err());
     }
-}
 
-pub fn get_assignments(line: String) -> ((u8, u8), (u8, u8)) {
-    let pair: Vec<(u8, u8)> = line
-    .split(',')
-    .map(|s| { //3-6
-        let sections: Vec<u8> = s
-        .split('-')
-        .map(|s| s.parse::<u8>().unwrap())
-        .collect::<Vec<_>>();
-
-        (sections.get(0).unwrap().clone(), sections.get(1).unwrap().clone())
-    })
-    .collect::<Vec<_>>();
-
-    ((pair[0].0, pair[0].1),(pair[1].0 , pair[1].1))
-}
\ No newline at end of file
+    #[test]
+    fn generated_pairs() {
+        for seed in 0..20 {
+            let input = generate(seed, 1000);
+            let classifications: Vec<Classification> = input
+                .lines()
+                .map(|line| {
+                    let (left, right) = parse_pair(line).unwrap();
+                    Classification::of(&left, &right)
+                })
+                .collect();
+
+            assert_eq!(classifications.len(), 1000);
+            assert!(count_classes(&classifications).len() >= 4);
+        }
+    }
+
+    fn range() -> impl Strategy<Value = SectionRange> {
+        (0u32..64, 0u32..64).prop_map(|(a, b)| SectionRange::new(a.min(b), a.max(b)).unwrap())
+    }
+
+    fn sections(range: &SectionRange) -> std::collections::BTreeSet<u32> {
+        (range.start..=range.end).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn classification_matches_section_sets(left in range(), right in range()) {
+            let (l, r) = (sections(&left), sections(&right));
+            let expected = if l == r {
+                Classification::Equal
+            } else if l.is_superset(&r) {
+                Classification::LeftContainsRight
+            } else if r.is_superset(&l) {
+                Classification::RightContainsLeft
+            } else if l.is_disjoint(&r) {
+                Classification::Disjoint
+            } else {
+                Classification::Overlapping
+            };
+
+            prop_assert_eq!(Classification::of(&left, &right), expected);
+        }
+
+        #[test]
+        fn classification_is_symmetric(left in range(), right in range()) {
+            let mirrored = match Classification::of(&left, &right) {
+                Classification::LeftContainsRight => Classification::RightContainsLeft,
+                Classification::RightContainsLeft => Classification::LeftContainsRight,
+                class => class,
+            };
+
+            prop_assert_eq!(Classification::of(&right, &left), mirrored);
+        }
+    }
+}
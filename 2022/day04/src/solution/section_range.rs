@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+/// Inclusive range of section IDs, e.g. `2-4`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Classification {
+    Disjoint,
+    Overlapping,
+    LeftContainsRight,
+    RightContainsLeft,
+    Equal,
+}
+
+impl SectionRange {
+    pub fn new(start: u32, end: u32) -> Result<Self, String> {
+        if start <= end {
+            Ok(Self { start, end })
+        } else {
+            Err(format!("Range {}-{} ends before it starts", start, end))
+        }
+    }
+
+    pub fn contains(&self, other: &SectionRange) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &SectionRange) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+}
+
+impl FromStr for SectionRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Expected \"<start>-<end>\", got {:?}", s);
+
+        let (start, end) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let start = start.parse::<u32>().map_err(|_| invalid())?;
+        let end = end.parse::<u32>().map_err(|_| invalid())?;
+
+        SectionRange::new(start, end)
+    }
+}
+
+impl Classification {
+    /// How the assignments of a pair of elves relate to each other.
+    pub fn of(left: &SectionRange, right: &SectionRange) -> Self {
+        match (left.contains(right), right.contains(left)) {
+            (true, true) => Classification::Equal,
+            (true, false) => Classification::LeftContainsRight,
+            (false, true) => Classification::RightContainsLeft,
+            (false, false) if left.overlaps(right) => Classification::Overlapping,
+            (false, false) => Classification::Disjoint,
+        }
+    }
+
+    /* Part 1 */
+    pub fn is_fully_contained(self) -> bool {
+        matches!(
+            self,
+            Classification::LeftContainsRight
+                | Classification::RightContainsLeft
+                | Classification::Equal
+        )
+    }
+
+    /* Part 2 */
+    pub fn is_overlapping(self) -> bool {
+        self != Classification::Disjoint
+    }
+}
+
+/// Parses a `<start>-<end>,<start>-<end>` line.
+pub fn parse_pair(line: &str) -> Result<(SectionRange, SectionRange), String> {
+    let (left, right) = line
+        .split_once(',')
+        .ok_or_else(|| format!("Expected a pair of ranges, got {:?}", line))?;
+
+    Ok((left.parse()?, right.parse()?))
+}
@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+const STACKS: usize = 9;
+
+/// Nine stacks of up to eight crates and `size` moves. Moves only take
+/// crates a stack holds, so every crane model can run the procedure.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut stacks: Vec<Vec<char>> = (0..STACKS)
+        .map(|_| {
+            (0..rng.index(0..=8))
+                .map(|_| (b'A' + rng.below(26) as u8) as char)
+                .collect()
+        })
+        .collect();
+    if stacks.iter().all(Vec::is_empty) {
+        stacks[0].push('A');
+    }
+
+    let mut input = String::new();
+    let height = stacks.iter().map(Vec::len).max().unwrap();
+    for level in (0..height).rev() {
+        let row = stacks
+            .iter()
+            .map(|stack| match stack.get(level) {
+                Some(name) => format!("[{}]", name),
+                None => "   ".to_string(),
+            })
+            .collect::<Vec<_>>();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+    let labels = (1..=STACKS)
+        .map(|label| format!(" {} ", label))
+        .collect::<Vec<_>>();
+    writeln!(input, "{}\n", labels.join(" ")).unwrap();
+
+    let mut heights = stacks.iter().map(Vec::len).collect::<Vec<_>>();
+    for _ in 0..size {
+        let filled = (0..STACKS)
+            .filter(|&ix| heights[ix] > 0)
+            .collect::<Vec<_>>();
+        let from = *rng.pick(&filled);
+        let to = (from + rng.index(1..=STACKS - 1)) % STACKS;
+        let amount = rng.index(1..=heights[from]);
+
+        heights[from] -= amount;
+        heights[to] += amount;
+        writeln!(input, "move {} from {} to {}", amount, from + 1, to + 1).unwrap();
+    }
+
+    input
+}
@@ -1,15 +1,21 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::BufReader;
 use std::{env, time::Instant};
 
-use configuration::Configuration;
+use configuration::{Configuration, Part};
 
 fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
 
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -21,22 +27,56 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `<input> [--part 1 | 2 | both] [--crane <model>] [--animate] [--log <path>]`,
+/// both parts by default. `--crane` runs a single crane model instead of the
+/// parts, `--animate` draws the stacks after every move and `--log` writes
+/// the executed moves to a file.
+/// `--generate <seed> <size>` prints nine random stacks and `size` moves
+/// instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason),
-        };
-        Configuration {
-            input_file_buffer: Some(BufReader::new(input_file.unwrap())),
-        }
-    } else {
-        Configuration {
-            input_file_buffer: None,
+    let mut config = Configuration {
+        input_file_buffer: None,
+        part: Part::Both,
+        animate: false,
+        log_path: None,
+        crane: None,
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                config.part = match args.next().as_deref().and_then(Part::from_arg) {
+                    Some(part) => part,
+                    None => panic!("Expected 1, 2 or both after --part"),
+                };
+            }
+            "--crane" => match args.next() {
+                Some(model) => config.crane = Some(model),
+                None => panic!("Expected a crane model after --crane"),
+            },
+            "--animate" => config.animate = true,
+            "--log" => match args.next() {
+                Some(path) => config.log_path = Some(path),
+                None => panic!("Expected a path after --log"),
+            },
+            "--generate" => match input_gen::parse_args(&mut args) {
+                Some(generate) => config.generate = Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            },
+            _ => {
+                let input_file = match File::open(&arg) {
+                    Ok(f) => f,
+                    Err(reason) => panic!("Couldn't open file {:?}: {}", arg, reason),
+                };
+                config.input_file_buffer = Some(BufReader::new(input_file));
+            }
         }
     }
+
+    config
 }
@@ -1,129 +1,235 @@
 use crate::configuration::Configuration;
 use std::{
-    collections::VecDeque,
-    fs::File,
-    io::{BufRead, BufReader},
+    fs,
+    io::{BufRead, Write},
 };
 
-#[allow(dead_code)]
-enum InstructionMethod {
-    FiFo,
-    FiLo,
-}
+use self::{
+    cranes::{Chunk, CraneModel, OneByOne},
+    crate_yard::{CrateYard, Move, Stacks},
+};
+
+mod cranes;
+mod crate_yard;
+mod parser;
 
 pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        // Part 1
-        //let im: InstructionMethod = InstructionMethod::FiFo;
-        // Part 2
-        let im: InstructionMethod = InstructionMethod::FiLo;
-        let result_stacks = execute_instructions(parse_file(config.input_file_buffer.unwrap()), im);
-
-        Ok(get_readout(result_stacks))
+    if let Some(input_buf) = config.input_file_buffer {
+        let lines = input_buf
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| format!("Couldn't read line: {}", e))?;
+        let (stacks, instructions) = parser::parse(&lines)?;
+
+        if let Some(path) = &config.log_path {
+            fs::File::create(path).map_err(|e| format!("Couldn't create {}: {}", path, e))?;
+        }
+
+        let solve = |name: &str, crane: &dyn CraneModel| -> Result<String, String> {
+            let mut yard = execute_instructions(stacks.clone(), &instructions, crane)?;
+
+            if config.animate {
+                yard.seek(0);
+                println!("{} - step 0/{}\n{}", name, yard.len(), yard.render());
+                while yard.redo() {
+                    println!(
+                        "{} - step {}/{}\n{}",
+                        name,
+                        yard.step(),
+                        yard.len(),
+                        yard.render()
+                    );
+                }
+            }
+            if let Some(path) = &config.log_path {
+                let mut file = fs::OpenOptions::new()
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("Couldn't open {}: {}", path, e))?;
+                write!(file, "# {}\n{}", name, yard.move_log())
+                    .map_err(|e| format!("Couldn't write move log: {}", e))?;
+            }
+
+            Ok(yard.readout())
+        };
+
+        match &config.crane {
+            Some(name) => {
+                let crane = cranes::from_name(name)?;
+                let name = format!("Crane {}", crane.name());
+                Ok(format!("{}: {}", name, solve(&name, crane.as_ref())?))
+            }
+            None => config
+                .part
+                .try_report(|| solve("Part 1", &OneByOne), || solve("Part 2", &Chunk)),
+        }
     } else {
         panic!("Could not open file");
     }
 }
 
-pub fn parse_file(b: BufReader<File>) -> (Vec<Vec<char>>, VecDeque<(i32, i32, i32)>) {
-    let mut buffer = b.lines().map(|s| s.unwrap()).peekable();
-
-    // Accounting for the missing ifnal whitespace, each stack is 4 chars long
-    let stack_count: i32 = buffer.peek().unwrap().chars().count() as i32 + 1 / 4;
-
-    let mut next_part: bool = false;
-    let mut crates: VecDeque<String> = VecDeque::new();
-    let mut instructions: VecDeque<(i32, i32, i32)> = VecDeque::new();
-    let mut stacks: Vec<Vec<char>> = Vec::with_capacity(stack_count as usize);
-
-    for line in buffer {
-        if line != "" {
-            if line.get(0..2).unwrap() == " 1" {
-                next_part = true;
-                continue;
-            };
-            if !next_part {
-                let mut temp: String = String::new();
-                let mut s = line.chars();
-                for _ in 0..stack_count {
-                    if let Some(c) = s.nth(1) {
-                        temp.push(c);
-                    }
-                }
-                crates.push_front(temp);
-            } else {
-                let items: Vec<&str> = line.split_whitespace().collect::<Vec<_>>();
-                instructions.push_back((
-                    items.get(1).unwrap().parse::<i32>().unwrap(),
-                    items.get(3).unwrap().parse::<i32>().unwrap(),
-                    items.get(5).unwrap().parse::<i32>().unwrap(),
-                ));
-            }
-        }
+fn execute_instructions(
+    stacks: Stacks,
+    instructions: &[Move],
+    crane: &dyn CraneModel,
+) -> Result<CrateYard, String> {
+    let mut yard = CrateYard::new(stacks);
+
+    for &instruction in instructions {
+        yard.execute(instruction, crane)?;
     }
 
-    for _ in 0..stack_count {
-        stacks.push(Vec::new())
+    Ok(yard)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{
+        cranes::{self, Alternating, Capacity, Chunk, OneByOne},
+        crate_yard::{CrateYard, Move, Stacks},
+        execute_instructions, parser,
+    };
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(str::to_string).collect()
     }
 
-    for level in &crates {
-        let iter = level
-            .chars()
-            .enumerate()
-            .filter(|&(i, _)| i % 2 == 0)
-            .map(|(_, c)| c)
-            .enumerate();
-        for (i, c) in iter {
-            match c {
-                ' ' => continue,
-                _ => stacks.get_mut(i).unwrap().push(c),
-            };
-        }
+    fn example() -> (Stacks, Vec<Move>) {
+        parser::parse(&lines(include_str!("../test.txt"))).unwrap()
     }
 
-    (stacks, instructions)
-}
+    #[test]
+    fn example_readouts() {
+        let (stacks, instructions) = example();
 
-/*
- * Here, as_chunk indicates if crates are taken from the stacks one-by-one or as a as_chunk.
- * In practice, the temp_deque should change between FiFo (one-by-one) or FiLo (as a chunk)*/
-fn execute_instructions(
-    (mut stacks, instructions): (Vec<Vec<char>>, VecDeque<(i32, i32, i32)>),
-    as_chunk: InstructionMethod,
-) -> Vec<Vec<char>> {
-    for (amnt, src, dst) in instructions.iter() {
-        let mut temp_deque: VecDeque<char> = VecDeque::with_capacity(*amnt as usize);
-        {
-            let src_stack = stacks.get_mut(*src as usize - 1).unwrap();
-            for _ in 0..*amnt {
-                if let Some(c) = src_stack.pop() {
-                    temp_deque.push_back(c);
-                }
-            }
-        }
-        {
-            let dst_stack = stacks.get_mut(*dst as usize - 1).unwrap();
-            for _ in 0..temp_deque.len() {
-                let c = match as_chunk {
-                    InstructionMethod::FiFo => temp_deque.pop_front().unwrap(),
-                    InstructionMethod::FiLo => temp_deque.pop_back().unwrap(),
-                };
-                dst_stack.push(c.clone());
-            }
-        }
+        let yard = execute_instructions(stacks.clone(), &instructions, &OneByOne);
+        assert_eq!(yard.unwrap().readout(), "CMZ");
+        let yard = execute_instructions(stacks, &instructions, &Chunk);
+        assert_eq!(yard.unwrap().readout(), "MCD");
     }
 
-    stacks
-}
+    #[test]
+    fn replay_backward_and_forward() {
+        let (stacks, instructions) = example();
+        let start = CrateYard::new(stacks.clone()).render();
+        let mut yard = execute_instructions(stacks, &instructions, &OneByOne).unwrap();
+        let end = yard.render();
 
-fn get_readout(stacks: Vec<Vec<char>>) -> String {
-    let mut s = String::new();
+        yard.seek(0);
+        assert_eq!(yard.render(), start);
+        assert_eq!(
+            start,
+            "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n"
+        );
 
-    for mut stack in stacks {
-        if let Some(c) = stack.pop() {
-            s.push(c);
-        }
+        yard.seek(yard.len());
+        assert_eq!(yard.render(), end);
+        assert_eq!(yard.readout(), "CMZ");
+    }
+
+    #[test]
+    fn move_to_missing_stack() {
+        let mut yard = CrateYard::new(vec![vec!["A".to_string()]]);
+        let instruction = Move {
+            amount: 1,
+            from: 1,
+            to: 2,
+        };
+
+        assert!(yard.execute(instruction, &OneByOne).is_err());
+    }
+
+    #[test]
+    fn more_than_nine_stacks_and_long_labels() {
+        let input = "\
+[A]                                         [K]
+[B] [C] [D] [E] [F] [G] [H] [I] [J] [XY]   [L]
+ 1   2   3   4   5   6   7   8   9   10     11
+
+move 1 from 10 to 11
+move 2 from 11 to 1";
+        let (stacks, moves) = parser::parse(&lines(input)).unwrap();
+
+        assert_eq!(stacks.len(), 11);
+        assert_eq!(stacks[0], vec!["B", "A"]);
+        assert_eq!(stacks[9], vec!["XY"]);
+        assert_eq!(stacks[10], vec!["L", "K"]);
+        assert_eq!(moves.len(), 2);
+
+        let yard = execute_instructions(stacks, &moves, &Chunk).unwrap();
+        assert_eq!(yard.readout(), "XYCDEFGHIJL");
+
+        let (redrawn, _) = parser::parse(&lines(&yard.render())).unwrap();
+        assert_eq!(CrateYard::new(redrawn).render(), yard.render());
     }
 
-    s
+    #[test]
+    fn move_referencing_missing_stack() {
+        let input = "[A]\n 1\n\nmove 1 from 1 to 2";
+
+        assert_eq!(
+            parser::parse(&lines(input)),
+            Err("Line 4: stack 2 doesn't exist, there are 1 stacks".to_string())
+        );
+    }
+
+    #[test]
+    fn capacity_limited_cranes() {
+        let stacks = vec![
+            ["A", "B", "C", "D", "E"].map(str::to_string).to_vec(),
+            vec![],
+        ];
+        let instructions = [Move {
+            amount: 5,
+            from: 1,
+            to: 2,
+        }];
+        let landed = |crane: &dyn cranes::CraneModel| {
+            let yard = execute_instructions(stacks.clone(), &instructions, crane).unwrap();
+            let (redrawn, _) = parser::parse(&lines(&yard.render())).unwrap();
+            redrawn[1].concat()
+        };
+
+        assert_eq!(landed(&OneByOne), "EDCBA");
+        assert_eq!(landed(&Chunk), "ABCDE");
+        assert_eq!(landed(&Capacity::new(2).unwrap()), "DEBCA");
+        assert_eq!(landed(&Capacity::new(5).unwrap()), "ABCDE");
+        assert_eq!(landed(&Alternating::new(2).unwrap()), "DECBA");
+        assert!(Capacity::new(0).is_err());
+        assert!(Alternating::new(0).is_err());
+        assert!(cranes::from_name("capacity:0").is_err());
+        assert!(cranes::from_name("alternating").is_err());
+    }
+
+    #[test]
+    fn popping_from_empty_stack_fails() {
+        let stacks = vec![vec!["A".to_string()], vec![]];
+        let instructions = [Move {
+            amount: 2,
+            from: 1,
+            to: 2,
+        }];
+
+        assert_eq!(
+            execute_instructions(stacks, &instructions, &OneByOne).err(),
+            Some("Step 1: can't take 2 crates from stack 1, it holds 1".to_string())
+        );
+    }
+
+    #[test]
+    fn generated_procedures() {
+        for seed in 0..20 {
+            let (stacks, instructions) = parser::parse(&lines(&generate(seed, 500))).unwrap();
+            let crates = stacks.iter().map(Vec::len).sum::<usize>();
+
+            assert_eq!(stacks.len(), 9);
+            assert_eq!(instructions.len(), 500);
+            for crane in [&OneByOne as &dyn cranes::CraneModel, &Chunk] {
+                let yard = execute_instructions(stacks.clone(), &instructions, crane).unwrap();
+                assert!(!yard.readout().is_empty());
+                assert!(yard.readout().len() <= crates.min(9));
+            }
+        }
+    }
 }
@@ -0,0 +1,118 @@
+use super::crate_yard::Crate;
+
+/// How a crane lands the crates it takes from a stack.
+pub trait CraneModel {
+    fn name(&self) -> String;
+
+    /// Receives the crates in the order they were taken from the source
+    /// stack (top first) and returns them in the order they are pushed on
+    /// the destination stack.
+    fn arrange(&self, taken: Vec<Crate>) -> Vec<Crate>;
+}
+
+/// CrateMover 9000: lifts one crate at a time.
+pub struct OneByOne;
+
+/// CrateMover 9001: lifts the whole chunk at once, keeping its order.
+pub struct Chunk;
+
+/// Lifts at most `capacity` crates at a time, keeping the order of each lift.
+pub struct Capacity(usize);
+
+/// Lifts at most `capacity` crates at a time and flips every other lift,
+/// starting with the second one.
+pub struct Alternating(usize);
+
+impl Capacity {
+    pub fn new(capacity: usize) -> Result<Self, String> {
+        lift_size(capacity).map(Capacity)
+    }
+}
+
+impl Alternating {
+    pub fn new(capacity: usize) -> Result<Self, String> {
+        lift_size(capacity).map(Alternating)
+    }
+}
+
+/// A crane that lifts nothing would never empty the source stack.
+fn lift_size(capacity: usize) -> Result<usize, String> {
+    match capacity {
+        0 => Err("Invalid crane capacity: 0".to_string()),
+        capacity => Ok(capacity),
+    }
+}
+
+impl CraneModel for OneByOne {
+    fn name(&self) -> String {
+        "one-by-one".to_string()
+    }
+
+    fn arrange(&self, taken: Vec<Crate>) -> Vec<Crate> {
+        taken
+    }
+}
+
+impl CraneModel for Chunk {
+    fn name(&self) -> String {
+        "chunk".to_string()
+    }
+
+    fn arrange(&self, mut taken: Vec<Crate>) -> Vec<Crate> {
+        taken.reverse();
+        taken
+    }
+}
+
+impl CraneModel for Capacity {
+    fn name(&self) -> String {
+        format!("capacity:{}", self.0)
+    }
+
+    fn arrange(&self, taken: Vec<Crate>) -> Vec<Crate> {
+        taken
+            .chunks(self.0)
+            .flat_map(|lift| lift.iter().rev().cloned())
+            .collect()
+    }
+}
+
+impl CraneModel for Alternating {
+    fn name(&self) -> String {
+        format!("alternating:{}", self.0)
+    }
+
+    fn arrange(&self, taken: Vec<Crate>) -> Vec<Crate> {
+        taken
+            .chunks(self.0)
+            .enumerate()
+            .flat_map(|(i, lift)| {
+                let mut lift = lift.to_vec();
+                if i % 2 == 0 {
+                    lift.reverse();
+                }
+                lift
+            })
+            .collect()
+    }
+}
+
+/// Parses `one-by-one` (or `9000`), `chunk` (or `9001`), `capacity:<k>` and
+/// `alternating:<k>`.
+pub fn from_name(name: &str) -> Result<Box<dyn CraneModel>, String> {
+    let (model, capacity) = match name.split_once(':') {
+        Some((model, capacity)) => match capacity.parse::<usize>() {
+            Ok(capacity) => (model, Some(capacity)),
+            Err(_) => return Err(format!("Invalid crane capacity: {}", capacity)),
+        },
+        None => (name, None),
+    };
+
+    match (model, capacity) {
+        ("one-by-one" | "9000", None) => Ok(Box::new(OneByOne)),
+        ("chunk" | "9001", None) => Ok(Box::new(Chunk)),
+        ("capacity", Some(capacity)) => Ok(Box::new(Capacity::new(capacity)?)),
+        ("alternating", Some(capacity)) => Ok(Box::new(Alternating::new(capacity)?)),
+        _ => Err(format!("Unknown crane model: {}", name)),
+    }
+}
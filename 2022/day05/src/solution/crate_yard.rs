@@ -0,0 +1,208 @@
+use std::fmt::Write;
+
+use super::cranes::CraneModel;
+
+/// A `move <amount> from <from> to <to>` instruction. Stacks are numbered
+/// from 1, as in the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub amount: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+pub type Crate = String;
+pub type Stacks = Vec<Vec<Crate>>;
+
+/// Crates taken from the source stack (top first) and the order in which
+/// they landed on the destination stack (bottom first), so a step can be
+/// undone exactly whatever way the crane moved them.
+#[derive(Debug, Clone)]
+struct Step {
+    instruction: Move,
+    taken: Vec<Crate>,
+    landed: Vec<Crate>,
+}
+
+/// Supply stacks that keep a record of every executed move and can be
+/// rewound and replayed.
+#[derive(Debug, Clone)]
+pub struct CrateYard {
+    stacks: Stacks,
+    history: Vec<Step>,
+    cursor: usize,
+}
+
+impl CrateYard {
+    pub fn new(stacks: Stacks) -> Self {
+        Self {
+            stacks,
+            history: vec![],
+            cursor: 0,
+        }
+    }
+
+    /// Executes `instruction` with `crane` after the current step,
+    /// discarding any undone steps. Fails without changing the stacks if the
+    /// source stack doesn't hold enough crates.
+    pub fn execute(&mut self, instruction: Move, crane: &dyn CraneModel) -> Result<(), String> {
+        self.stack_index(instruction.from)?;
+        self.stack_index(instruction.to)?;
+
+        let src = &mut self.stacks[instruction.from - 1];
+        if src.len() < instruction.amount {
+            return Err(format!(
+                "Step {}: can't take {} crates from stack {}, it holds {}",
+                self.cursor + 1,
+                instruction.amount,
+                instruction.from,
+                src.len()
+            ));
+        }
+        let taken: Vec<Crate> = src.drain(src.len() - instruction.amount..).rev().collect();
+        let landed = crane.arrange(taken.clone());
+        self.stacks[instruction.to - 1].extend(landed.iter().cloned());
+
+        self.history.truncate(self.cursor);
+        self.history.push(Step {
+            instruction,
+            taken,
+            landed,
+        });
+        self.cursor += 1;
+
+        Ok(())
+    }
+
+    fn stack_index(&self, label: usize) -> Result<usize, String> {
+        if label == 0 || label > self.stacks.len() {
+            Err(format!(
+                "Stack {} doesn't exist, there are {} stacks",
+                label,
+                self.stacks.len()
+            ))
+        } else {
+            Ok(label - 1)
+        }
+    }
+
+    /// Reverts the last executed step. Returns false if there is none.
+    pub fn undo(&mut self) -> bool {
+        if self.cursor == 0 {
+            return false;
+        }
+        self.cursor -= 1;
+
+        let step = &self.history[self.cursor];
+        let dst = &mut self.stacks[step.instruction.to - 1];
+        dst.truncate(dst.len() - step.landed.len());
+        self.stacks[step.instruction.from - 1].extend(step.taken.iter().rev().cloned());
+
+        true
+    }
+
+    /// Re-executes the next undone step. Returns false if there is none.
+    pub fn redo(&mut self) -> bool {
+        if self.cursor == self.history.len() {
+            return false;
+        }
+
+        let step = &self.history[self.cursor];
+        let src = &mut self.stacks[step.instruction.from - 1];
+        src.truncate(src.len() - step.taken.len());
+        self.stacks[step.instruction.to - 1].extend(step.landed.iter().cloned());
+        self.cursor += 1;
+
+        true
+    }
+
+    /// Moves to the state after `step` executed moves, 0 being the
+    /// starting drawing.
+    pub fn seek(&mut self, step: usize) {
+        while self.cursor > step && self.undo() {}
+        while self.cursor < step && self.redo() {}
+    }
+
+    pub fn step(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn len(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Crates on top of every stack, skipping empty stacks.
+    pub fn readout(&self) -> String {
+        self.stacks
+            .iter()
+            .filter_map(|stack| stack.last())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Draws the stacks in the same format as the puzzle input. Columns
+    /// widen to fit multi-character crates and labels.
+    pub fn render(&self) -> String {
+        let height = self.stacks.iter().map(Vec::len).max().unwrap_or(0);
+        let widths = self
+            .stacks
+            .iter()
+            .enumerate()
+            .map(|(i, stack)| {
+                let widest_crate = stack.iter().map(|c| c.chars().count() + 2).max();
+                let label_width = (i + 1).to_string().len();
+                widest_crate.unwrap_or(0).max(label_width).max(3)
+            })
+            .collect::<Vec<_>>();
+        let mut out = String::new();
+
+        for level in (0..height).rev() {
+            let row = self
+                .stacks
+                .iter()
+                .zip(widths.iter())
+                .map(|(stack, &width)| match stack.get(level) {
+                    Some(c) => format!("{:^width$}", format!("[{}]", c)),
+                    None => " ".repeat(width),
+                })
+                .collect::<Vec<_>>();
+            writeln!(out, "{}", row.join(" ")).unwrap();
+        }
+
+        let labels = widths
+            .iter()
+            .enumerate()
+            .map(|(i, &width)| format!("{:^width$}", i + 1))
+            .collect::<Vec<_>>();
+        writeln!(out, "{}", labels.join(" ")).unwrap();
+
+        out
+    }
+
+    /// One line per recorded step, with the crates as they landed. Undone
+    /// steps are marked as such.
+    pub fn move_log(&self) -> String {
+        let mut out = String::new();
+
+        for (i, step) in self.history.iter().enumerate() {
+            let crates = step
+                .landed
+                .iter()
+                .map(|c| format!("[{}]", c))
+                .collect::<Vec<_>>();
+            writeln!(
+                out,
+                "{:>4}: move {} from {} to {} => {}{}",
+                i + 1,
+                step.instruction.amount,
+                step.instruction.from,
+                step.instruction.to,
+                crates.join(" "),
+                if i < self.cursor { "" } else { " (undone)" }
+            )
+            .unwrap();
+        }
+
+        out
+    }
+}
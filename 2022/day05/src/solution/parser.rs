@@ -0,0 +1,120 @@
+use super::crate_yard::{Move, Stacks};
+
+/// Parses the stack drawing and the rearrangement procedure.
+///
+/// Stack columns are taken from the numbered label row below the drawing, so
+/// any amount of stacks, multi-digit labels, ragged trailing whitespace and
+/// multi-character crates (`[AB]`) are supported. A crate belongs to the
+/// stack whose label overlaps its brackets.
+pub fn parse(lines: &[String]) -> Result<(Stacks, Vec<Move>), String> {
+    let split = lines
+        .iter()
+        .position(|line| line.trim().is_empty())
+        .unwrap_or(lines.len());
+    let (drawing, procedure) = lines.split_at(split);
+
+    let (label_row, crate_rows) = drawing.split_last().ok_or("Missing stack drawing")?;
+    let labels = parse_labels(label_row)?;
+
+    let mut stacks: Stacks = vec![vec![]; labels.len()];
+    for (row_ix, row) in crate_rows.iter().enumerate().rev() {
+        for (start, end, name) in tokens(row) {
+            let name = name
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| format!("Line {}: invalid crate {:?}", row_ix + 1, name))?;
+            let stack = labels
+                .iter()
+                .position(|&(label_start, label_end)| label_start <= end && start <= label_end)
+                .ok_or_else(|| {
+                    format!(
+                        "Line {}: crate [{}] isn't above any stack label",
+                        row_ix + 1,
+                        name
+                    )
+                })?;
+
+            stacks[stack].push(name.to_string());
+        }
+    }
+
+    let mut moves = vec![];
+    for (line_ix, line) in procedure.iter().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line_number = split + line_ix + 1;
+        let instruction = parse_move(line)
+            .ok_or_else(|| format!("Line {}: invalid move {:?}", line_number, line))?;
+        for stack in [instruction.from, instruction.to] {
+            if stack == 0 || stack > stacks.len() {
+                return Err(format!(
+                    "Line {}: stack {} doesn't exist, there are {} stacks",
+                    line_number,
+                    stack,
+                    stacks.len()
+                ));
+            }
+        }
+
+        moves.push(instruction);
+    }
+
+    Ok((stacks, moves))
+}
+
+/// Character span of every label, checking they are numbered `1..=n`.
+fn parse_labels(row: &str) -> Result<Vec<(usize, usize)>, String> {
+    let mut labels = vec![];
+
+    for (start, end, label) in tokens(row) {
+        let expected = labels.len() + 1;
+        match label.parse::<usize>() {
+            Ok(n) if n == expected => labels.push((start, end)),
+            _ => {
+                return Err(format!(
+                    "Expected stack label {} in the label row, got {:?}",
+                    expected, label
+                ))
+            }
+        }
+    }
+
+    if labels.is_empty() {
+        Err("Missing stack label row".to_string())
+    } else {
+        Ok(labels)
+    }
+}
+
+/// Whitespace separated tokens with their first and last character index.
+fn tokens(line: &str) -> Vec<(usize, usize, &str)> {
+    let mut tokens = vec![];
+    let mut start: Option<(usize, usize)> = None;
+
+    for (char_ix, (byte_ix, c)) in line.char_indices().chain([(line.len(), ' ')]).enumerate() {
+        match (start, c.is_whitespace()) {
+            (None, false) => start = Some((char_ix, byte_ix)),
+            (Some((start_char, start_byte)), true) => {
+                tokens.push((start_char, char_ix - 1, &line[start_byte..byte_ix]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    tokens
+}
+
+fn parse_move(line: &str) -> Option<Move> {
+    match line.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["move", amount, "from", from, "to", to] => Some(Move {
+            amount: amount.parse().ok()?,
+            from: from.parse().ok()?,
+            to: to.parse().ok()?,
+        }),
+        _ => None,
+    }
+}
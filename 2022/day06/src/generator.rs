@@ -0,0 +1,25 @@
+use input_gen::Rng;
+
+const MESSAGE_MARKER: usize = 14;
+
+/// A datastream of `size` (at least 14) characters. The filler only uses
+/// thirteen letters, so the start-of-message marker is the one planted in
+/// the second half and it can't be found before scanning most of the input.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.max(MESSAGE_MARKER);
+
+    let mut stream = (0..size)
+        .map(|_| b'a' + rng.below(13) as u8)
+        .collect::<Vec<_>>();
+
+    let mut letters = (b'a'..=b'z').collect::<Vec<_>>();
+    rng.shuffle(&mut letters);
+    let last_start = size - MESSAGE_MARKER;
+    let start = rng.index((size / 2).min(last_start)..=last_start);
+    stream[start..start + MESSAGE_MARKER].copy_from_slice(&letters[..MESSAGE_MARKER]);
+
+    let mut input = String::from_utf8(stream).unwrap();
+    input.push('\n');
+    input
+}
@@ -1,15 +1,21 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::BufReader;
 use std::{env, time::Instant};
 
-use configuration::Configuration;
+use configuration::{Configuration, Part};
 
 fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
     
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -21,18 +27,46 @@ fn main() {
         Err(e) => println!("Error: {}", e)
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `<input> [--part 1 | 2 | both] [--markers N]...`, both parts by
+/// default. `--markers` lists every marker position for a window size.
+/// `--generate <seed> <size>` prints a random datastream of `size` characters
+/// instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason)
-        };
-        Configuration { input_file_buffer: Some(BufReader::new(input_file.unwrap())) }
-    } else { 
-        Configuration { input_file_buffer: None }
+    let mut config = Configuration {
+        input_file_buffer: None,
+        part: Part::Both,
+        marker_sizes: vec![],
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--part" {
+            config.part = match args.next().as_deref().and_then(Part::from_arg) {
+                Some(part) => part,
+                None => panic!("Expected 1, 2 or both after --part"),
+            };
+        } else if arg == "--markers" {
+            match args.next().map(|size| size.parse::<usize>()) {
+                Some(Ok(size)) if size > 0 => config.marker_sizes.push(size),
+                _ => panic!("Expected a positive window size after --markers"),
+            }
+        } else if arg == "--generate" {
+            config.generate = match input_gen::parse_args(&mut args) {
+                Some(generate) => Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            };
+        } else {
+            let input_file = match File::open(&arg) {
+                Ok(f) => f,
+                Err(reason) => panic!("Couldn't open file {:?}: {}", arg, reason),
+            };
+            config.input_file_buffer = Some(BufReader::new(input_file));
+        }
     }
+
+    config
 }
@@ -1,49 +1,92 @@
-use std::{io::{BufReader, Read}, fs::File};
 use crate::configuration::Configuration;
 
-pub fn run(config: Configuration) -> Result<String, String>{
-
-    if config.input_file_buffer.is_some() {
-        if let Some(buf) = config.input_file_buffer {
-            // Part 1
-            // let res = find_marker(buf, 4);
-            // Part 2
-            let res = find_marker(buf, 14);
-            Ok(res.to_string())
-        } else {
-            Err("Could not open file".to_string())
+mod marker;
+
+const START_OF_PACKET: usize = 4;
+const START_OF_MESSAGE: usize = 14;
+
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let mut sizes = vec![START_OF_PACKET, START_OF_MESSAGE];
+        sizes.extend(config.marker_sizes.iter());
+
+        let markers =
+            marker::scan(input_buf, &sizes).map_err(|e| format!("Could not read file: {}", e))?;
+        let first_marker = |ix: usize| {
+            markers[ix]
+                .first()
+                .map(usize::to_string)
+                .ok_or_else(|| format!("No marker of {} distinct characters", sizes[ix]))
+        };
+
+        let mut result = config
+            .part
+            .try_report(|| first_marker(0), || first_marker(1))?;
+        for (ix, size) in config.marker_sizes.iter().enumerate() {
+            let positions = &markers[ix + 2];
+            result.push_str(&format!(
+                "\nMarkers of size {} ({}): {:?}",
+                size,
+                positions.len(),
+                positions
+            ));
         }
+
+        Ok(result)
     } else {
         panic!("No file provided!");
     }
 }
 
-fn find_marker(mut transmission: BufReader<File>, pattern_size: usize) -> usize {
-    let mut start: usize = 0;
-    let mut buf: Vec<u8> = vec![];
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
 
-    if let Ok(_) = transmission.read_to_end(&mut buf) {
-        for (i, chars) in buf.windows(pattern_size).enumerate() {
-            if is_start(chars) { 
-                start = i; 
-                break; 
-            }
-        }
+    use crate::generator::generate;
+    use crate::solution::marker::scan;
 
-        // start + pattern_size as the iterator only iterates until the first element
-        start + pattern_size
-    } else {
-        0
+    fn first(transmission: &str, size: usize) -> Option<usize> {
+        scan(transmission.as_bytes(), &[size]).unwrap()[0]
+            .first()
+            .copied()
     }
-}
 
-fn is_start(s: &[u8]) -> bool {
-    let mut are_duplicates = false;
-    for i in 0..s.len() {
-        for k in i + 1..s.len() {
-            if s[i] == s[k] { are_duplicates = true; }
-        }
+    #[test]
+    fn examples() {
+        assert_eq!(first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 4), Some(7));
+        assert_eq!(first("bvwbjplbgvbhsrlpgdmjqwftvncz", 4), Some(5));
+        assert_eq!(first("nppdvjthqldpwncqszvftbrmjlhg", 4), Some(6));
+        assert_eq!(first("mjqjpqmgbljsphdztnvjfqwrcgsmlb", 14), Some(19));
+        assert_eq!(first("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 14), Some(26));
     }
 
-    !are_duplicates
+    #[test]
+    fn every_marker_and_no_marker() {
+        let markers = scan("aabcab\n".as_bytes(), &[3, 4]).unwrap();
+
+        assert_eq!(markers[0], vec![4, 5, 6]);
+        assert_eq!(markers[1], Vec::<usize>::new());
+        assert_eq!(first("", 1), None);
+    }
+
+    #[test]
+    fn generated_datastreams() {
+        for seed in 0..20 {
+            let input = generate(seed, 2000);
+            let bytes = input.trim_end().as_bytes();
+            let markers = scan(input.as_bytes(), &[4, 14]).unwrap();
+
+            for (ix, size) in [4, 14].into_iter().enumerate() {
+                let expected = bytes
+                    .windows(size)
+                    .enumerate()
+                    .filter(|(_, window)| window.iter().collect::<HashSet<_>>().len() == size)
+                    .map(|(start, _)| start + size)
+                    .collect::<Vec<_>>();
+
+                assert!(!expected.is_empty());
+                assert_eq!(markers[ix], expected);
+            }
+        }
+    }
 }
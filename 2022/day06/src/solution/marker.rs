@@ -0,0 +1,83 @@
+use std::{
+    collections::VecDeque,
+    io::{self, Read},
+};
+
+/// Sliding window over the last `size` characters of a transmission, with a
+/// count of every character in it. The window is a marker when it is full
+/// and holds no repeated character.
+pub struct MarkerDetector {
+    size: usize,
+    window: VecDeque<u8>,
+    counts: [usize; 256],
+    repeated: usize,
+    processed: usize,
+}
+
+impl MarkerDetector {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            window: VecDeque::with_capacity(size + 1),
+            counts: [0; 256],
+            repeated: 0,
+            processed: 0,
+        }
+    }
+
+    /// Feeds the next character. Returns the amount of characters processed
+    /// so far if they end with a marker.
+    pub fn push(&mut self, c: u8) -> Option<usize> {
+        self.window.push_back(c);
+        self.counts[c as usize] += 1;
+        if self.counts[c as usize] == 2 {
+            self.repeated += 1;
+        }
+
+        if self.window.len() > self.size {
+            let old = self.window.pop_front().unwrap() as usize;
+            self.counts[old] -= 1;
+            if self.counts[old] == 1 {
+                self.repeated -= 1;
+            }
+        }
+
+        self.processed += 1;
+        if self.window.len() == self.size && self.repeated == 0 {
+            Some(self.processed)
+        } else {
+            None
+        }
+    }
+}
+
+/// Every marker position for each of `sizes`, reading `transmission` once.
+/// Line breaks are not part of the transmission.
+pub fn scan(transmission: impl Read, sizes: &[usize]) -> io::Result<Vec<Vec<usize>>> {
+    let mut detectors: Vec<MarkerDetector> = sizes
+        .iter()
+        .map(|&size| MarkerDetector::new(size))
+        .collect();
+    let mut markers = vec![vec![]; sizes.len()];
+
+    let mut buf = [0u8; 8192];
+    let mut transmission = transmission;
+    loop {
+        let read = match transmission.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        for &c in buf[..read].iter().filter(|&&c| c != b'\n' && c != b'\r') {
+            for (detector, found) in detectors.iter_mut().zip(markers.iter_mut()) {
+                if let Some(position) = detector.push(c) {
+                    found.push(position);
+                }
+            }
+        }
+    }
+
+    Ok(markers)
+}
@@ -3,4 +3,9 @@ use std::io;
 
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub query: Vec<String>,
+    pub total_space: Option<u64>,
+    pub target_free_space: Option<u64>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+/// Used space the generated file sizes add up to on average, enough for the
+/// default disk to need a deletion.
+const USED_SPACE: u64 = 50_000_000;
+
+const EXTENSIONS: &[&str] = &["txt", "dat", "log", "lst", "ext"];
+
+struct Directory {
+    files: Vec<(String, u64)>,
+    children: Vec<(String, usize)>,
+}
+
+/// A transcript listing a tree of `size` directories below `/`, visiting
+/// them depth first with `cd <name>` and `cd ..` like the puzzle input.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let max_file_size = (USED_SPACE / size.max(1) as u64).max(1000);
+
+    let mut dirs: Vec<Directory> = vec![];
+    let mut names: Vec<HashSet<String>> = vec![];
+    for ix in 0..=size {
+        names.push(HashSet::new());
+        let mut files = vec![];
+        for _ in 0..rng.index(0..=4) {
+            let mut name = unique_name(&mut rng, &mut names[ix]);
+            if rng.chance(0.5) {
+                let extension = *rng.pick(EXTENSIONS);
+                name = format!("{}.{}", name, extension);
+            }
+            files.push((name, rng.between(1..=max_file_size)));
+        }
+        dirs.push(Directory {
+            files,
+            children: vec![],
+        });
+
+        if ix > 0 {
+            let parent = rng.index(0..=ix - 1);
+            let name = unique_name(&mut rng, &mut names[parent]);
+            dirs[parent].children.push((name, ix));
+        }
+    }
+
+    let mut transcript = "$ cd /\n".to_string();
+    explore(&dirs, 0, &mut rng, &mut transcript);
+    transcript
+}
+
+fn explore(dirs: &[Directory], ix: usize, rng: &mut Rng, transcript: &mut String) {
+    let mut listing = dirs[ix]
+        .children
+        .iter()
+        .map(|(name, _)| format!("dir {}", name))
+        .chain(
+            dirs[ix]
+                .files
+                .iter()
+                .map(|(name, size)| format!("{} {}", size, name)),
+        )
+        .collect::<Vec<_>>();
+    rng.shuffle(&mut listing);
+
+    transcript.push_str("$ ls\n");
+    for entry in listing {
+        writeln!(transcript, "{}", entry).unwrap();
+    }
+    for (name, child) in &dirs[ix].children {
+        writeln!(transcript, "$ cd {}", name).unwrap();
+        explore(dirs, *child, rng, transcript);
+        transcript.push_str("$ cd ..\n");
+    }
+}
+
+/// One to eight lowercase letters, unused among `taken`.
+fn unique_name(rng: &mut Rng, taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = (0..rng.index(1..=8))
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect::<String>();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::BufReader;
@@ -10,6 +11,11 @@ fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
 
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -21,22 +27,51 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `day07 <input> [--total-space N] [--target-free N] [query]`
+///
+/// Queries: `tree`, `du <path>`, `find -size <[+-]N>`, `plan [smallest | set]`
+///
+/// `day07 --generate <seed> <size>` prints a random transcript exploring
+/// `size` directories instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
+    let mut config = Configuration {
+        input_file_buffer: None,
+        query: vec![],
+        total_space: None,
+        target_free_space: None,
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1).peekable();
+    if let Some(path) = args.next_if(|arg| arg != "--generate") {
+        let input_file = match File::open(&path) {
+            Ok(f) => f,
             Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason),
         };
-        Configuration {
-            input_file_buffer: Some(BufReader::new(input_file.unwrap())),
-        }
-    } else {
-        Configuration {
-            input_file_buffer: None,
+        config.input_file_buffer = Some(BufReader::new(input_file));
+    }
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--total-space" => config.total_space = Some(parse_space(&arg, args.next())),
+            "--target-free" => config.target_free_space = Some(parse_space(&arg, args.next())),
+            "--generate" => match input_gen::parse_args(&mut args) {
+                Some(generate) => config.generate = Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            },
+            _ => config.query.push(arg),
         }
     }
+
+    config
+}
+
+fn parse_space(option: &str, value: Option<String>) -> u64 {
+    match value.as_deref().map(str::parse::<u64>) {
+        Some(Ok(space)) => space,
+        _ => panic!("Expected a size after {}", option),
+    }
 }
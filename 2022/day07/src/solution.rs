@@ -1,271 +1,236 @@
 use crate::configuration::Configuration;
-use std::{
-    cell::RefCell,
-    cmp::{Ordering, Reverse},
-    collections::BinaryHeap,
-    fs,
-    io::{BufRead, BufReader},
-    rc::{Rc, Weak},
+use std::io::BufRead;
+
+use self::{
+    filesystem::FileSystem,
+    planner::{DeletionPlanner, Plan, PlanMode},
 };
 
-type NodeLink = Rc<RefCell<Directory>>;
-type ParentNodeLink = Weak<RefCell<Directory>>;
+mod filesystem;
+mod planner;
 
-const TOTAL_SPACE: i32 = 70000000;
-const TARGET_FREE_SPACE: i32 = 30000000;
+const TOTAL_SPACE: u64 = 70000000;
+const TARGET_FREE_SPACE: u64 = 30000000;
 
-#[derive(Debug)]
-struct Tree {
-    root: NodeLink,
-    total_used_space: i32,
-}
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let lines = input_buf
+            .lines()
+            .collect::<Result<Vec<String>, _>>()
+            .map_err(|e| e.to_string())?;
+        let fs = FileSystem::replay(lines.iter().map(String::as_str))?;
+        let planner = DeletionPlanner {
+            total_space: config.total_space.unwrap_or(TOTAL_SPACE),
+            target_free_space: config.target_free_space.unwrap_or(TARGET_FREE_SPACE),
+        };
 
-impl Tree {
-    fn new(root_name: String) -> Self {
-        Self {
-            root: Directory::new(root_name),
-            total_used_space: 0,
+        let query = config.query.iter().map(String::as_str).collect::<Vec<_>>();
+        match query.as_slice() {
+            [] => solve(&fs, &planner),
+            ["tree"] => Ok(format!("\n{}", fs.tree())),
+            ["du", path] => fs
+                .du(path)
+                .map(|size| format!("{}\t{}", size, path))
+                .ok_or_else(|| format!("No such directory: {}", path)),
+            ["find", "-size", size] => {
+                let predicate = parse_size_filter(size)?;
+                let mut found = fs.find_dirs(&predicate);
+                found.extend(fs.find_files(&predicate));
+                found.sort();
+
+                Ok(found
+                    .into_iter()
+                    .map(|(path, size)| format!("\n{}\t{}", size, path))
+                    .collect())
+            }
+            ["plan"] | ["plan", "smallest"] => describe_plan(&fs, &planner, PlanMode::Smallest),
+            ["plan", "set"] => describe_plan(&fs, &planner, PlanMode::MinimalSet),
+            _ => Err(format!("Unknown query: {}", config.query.join(" "))),
         }
+    } else {
+        Err("No input file given!".to_string())
     }
+}
 
-    fn add_subdir(parent_link: &NodeLink, name: String) {
-        let mut parent = parent_link.borrow_mut();
-        let subdir = Directory::new_with_parent(name, parent_link);
+fn solve(fs: &FileSystem, planner: &DeletionPlanner) -> Result<String, String> {
+    let part_1: u64 = fs
+        .find_dirs(|size| size <= 100000)
+        .into_iter()
+        .map(|(_, size)| size)
+        .sum();
 
-        parent.sub_directories.push(subdir);
-    }
+    // The directory to delete is shown by the `plan` query
+    let part_2 = plan_or_err(fs, planner, PlanMode::Smallest)?.freed;
 
-    pub fn sum_dir_size_if_up_to(&self, accumulator: &mut i32, parent: &NodeLink, threshold: i32) {
-        for subdir in parent.borrow_mut().sub_directories.iter() {
-            let size = subdir.borrow().size;
-            if size <= threshold {
-                *accumulator += size;
-            }
-            self.sum_dir_size_if_up_to(accumulator, subdir, threshold);
-        }
-    }
+    Ok(format!("\nPart 1: {}\nPart 2: {}", part_1, part_2))
+}
 
-    pub fn find_suitable_dirs_for_deletion(
-        &self,
-        collection: &mut BinaryHeap<Reverse<i32>>,
-        parent: &NodeLink,
-        free_space: i32,
-    ) {
-        for subdir in parent.borrow_mut().sub_directories.iter() {
-            let size = subdir.borrow().size;
-            if free_space + size >= TARGET_FREE_SPACE {
-                collection.push(Reverse(size));
-            }
-            self.find_suitable_dirs_for_deletion(collection, subdir, free_space);
-        }
+fn describe_plan(
+    fs: &FileSystem,
+    planner: &DeletionPlanner,
+    mode: PlanMode,
+) -> Result<String, String> {
+    let plan = plan_or_err(fs, planner, mode)?;
+
+    let mut out = format!(
+        "\nNeeded: {}\nFreed: {}",
+        planner.space_needed(fs),
+        plan.freed
+    );
+    for (path, size) in plan.directories {
+        out.push_str(&format!("\n{}\t{}", size, path));
     }
+
+    Ok(out)
 }
 
-#[derive(Debug)]
-struct Directory {
-    name: String,
-    files: BinaryHeap<File>,
-    parent_directory: Option<ParentNodeLink>,
-    sub_directories: BinaryHeap<NodeLink>,
-    size: i32,
+fn plan_or_err(fs: &FileSystem, planner: &DeletionPlanner, mode: PlanMode) -> Result<Plan, String> {
+    planner.plan(fs, mode).ok_or(format!(
+        "Can't free {} with a total space of {}",
+        planner.target_free_space, planner.total_space
+    ))
 }
 
-impl Directory {
-    fn new(name: String) -> NodeLink {
-        Rc::new(RefCell::new(Self {
-            name,
-            files: BinaryHeap::new(),
-            parent_directory: None,
-            sub_directories: BinaryHeap::new(),
-            size: 0,
-        }))
-    }
+/// `find -size` style filter: `+N` is larger than N, `-N` smaller than N and
+/// `N` exactly N.
+fn parse_size_filter(filter: &str) -> Result<impl Fn(u64) -> bool, String> {
+    let invalid = || format!("Invalid size filter: {}", filter);
 
-    fn new_with_parent(name: String, parent_directory: &NodeLink) -> NodeLink {
-        Rc::new(RefCell::new(Self {
-            name,
-            files: BinaryHeap::new(),
-            parent_directory: Some(Rc::downgrade(parent_directory)),
-            sub_directories: BinaryHeap::new(),
-            size: 0,
-        }))
-    }
+    let (ordering, amount) = match filter.as_bytes().first() {
+        Some(b'+') => (std::cmp::Ordering::Greater, &filter[1..]),
+        Some(b'-') => (std::cmp::Ordering::Less, &filter[1..]),
+        _ => (std::cmp::Ordering::Equal, filter),
+    };
+    let amount = amount.parse::<u64>().map_err(|_| invalid())?;
 
-    fn add_file(&mut self, name: String, size: i32) {
-        self.files.push(File::new(name, size));
-        self.mod_size(size);
-    }
-
-    fn mod_size(&mut self, by: i32) {
-        self.size += by;
-        if let Some(weak_parent) = &self.parent_directory {
-            if let Some(parent) = Weak::upgrade(weak_parent) {
-                parent.borrow_mut().mod_size(by);
-            }
-        };
-    }
+    Ok(move |size: u64| size.cmp(&amount) == ordering)
 }
 
-impl PartialEq for Directory {
-    fn eq(&self, other: &Self) -> bool {
-        self.name == other.name && self.size == other.size
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{
+        filesystem::FileSystem,
+        planner::{DeletionPlanner, Plan, PlanMode},
+        solve, TARGET_FREE_SPACE, TOTAL_SPACE,
+    };
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn example() {
+        let fs = FileSystem::replay(EXAMPLE.lines()).unwrap();
+
+        assert_eq!(fs.du("/a/e"), Some(584));
+        assert_eq!(fs.du("/a"), Some(94853));
+        assert_eq!(fs.du("/d"), Some(24933642));
+        assert_eq!(fs.used_space(), 48381165);
+        let planner = DeletionPlanner {
+            total_space: TOTAL_SPACE,
+            target_free_space: TARGET_FREE_SPACE,
+        };
+        assert_eq!(
+            solve(&fs, &planner).unwrap(),
+            "\nPart 1: 95437\nPart 2: 24933642"
+        );
     }
-}
 
-impl Eq for Directory {}
+    #[test]
+    fn relisting_does_not_double_count() {
+        let transcript = "$ cd /\n$ ls\n10 a\ndir b\n$ cd b\n$ ls\n5 c\n$ cd /\n$ ls\n10 a\ndir b\n$ cd /b\n$ ls\n5 c";
+        let fs = FileSystem::replay(transcript.lines()).unwrap();
 
-impl Ord for Directory {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.size.cmp(&other.size)
+        assert_eq!(fs.used_space(), 15);
+        assert_eq!(fs.du("/b"), Some(5));
     }
-}
 
-impl PartialOrd for Directory {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.name.partial_cmp(&other.name) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        self.size.partial_cmp(&other.size)
+    #[test]
+    fn absolute_paths_and_tree() {
+        let transcript = "$ cd /x/y\n$ ls\n3 z\n$ cd ../../w\n$ ls\n1 v";
+        let fs = FileSystem::replay(transcript.lines()).unwrap();
+
+        assert_eq!(fs.du("/x"), Some(3));
+        assert_eq!(
+            fs.tree(),
+            "- / (dir)\n  - w (dir)\n    - v (file, size=1)\n  - x (dir)\n    - y (dir)\n      - z (file, size=3)\n"
+        );
+        assert_eq!(
+            fs.find_files(|size| size > 2),
+            vec![("/x/y/z".to_string(), 3)]
+        );
     }
-}
 
-#[derive(Debug, PartialEq, Eq, PartialOrd)]
-struct File {
-    name: String,
-    file_size: i32,
-}
+    #[test]
+    fn minimal_set_of_non_nested_dirs() {
+        let transcript = "$ cd /\n$ ls\ndir a\ndir b\n$ cd a\n$ ls\n40 x\ndir c\n$ cd c\n$ ls\n30 y\n$ cd /b\n$ ls\n50 z";
+        let fs = FileSystem::replay(transcript.lines()).unwrap();
+        let planner = DeletionPlanner {
+            total_space: 130,
+            target_free_space: 100,
+        };
 
-impl File {
-    fn new(name: String, file_size: i32) -> Self {
-        File { name, file_size }
+        assert_eq!(planner.space_needed(&fs), 90);
+        assert_eq!(planner.plan(&fs, PlanMode::Smallest), None);
+        assert_eq!(
+            planner.plan(&fs, PlanMode::MinimalSet),
+            Some(Plan {
+                directories: vec![("/a".to_string(), 70), ("/b".to_string(), 50)],
+                freed: 120,
+            })
+        );
     }
-}
 
-impl Ord for File {
-    fn cmp(&self, other: &Self) -> Ordering {
-        self.file_size.cmp(&other.file_size)
+    #[test]
+    fn sizes_beyond_32_bits() {
+        let transcript = "$ cd /\n$ ls\n3000000000 disk.img\ndir vm\n$ cd vm\n$ ls\n5000000000000 a.qcow2\n4000000000000 b.qcow2";
+        let fs = FileSystem::replay(transcript.lines()).unwrap();
+
+        assert_eq!(fs.du("/vm"), Some(9_000_000_000_000));
+        assert_eq!(fs.used_space(), 9_003_000_000_000);
     }
-}
 
-pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        let tree = parse_file(config.input_file_buffer.unwrap());
+    #[test]
+    fn total_size_overflow_is_an_error() {
+        let transcript = format!("$ cd /\n$ ls\n{} a\ndir b\n$ cd b\n$ ls\n1 c", u64::MAX);
 
-        let mut accumulator: i32 = 0;
-        let mut size_heap: BinaryHeap<Reverse<i32>> = BinaryHeap::new();
-        tree.sum_dir_size_if_up_to(&mut accumulator, &tree.root, 100000);
+        assert!(FileSystem::replay(transcript.lines()).is_err());
+    }
 
-        tree.find_suitable_dirs_for_deletion(
-            &mut size_heap,
-            &tree.root,
-            TOTAL_SPACE - tree.total_used_space,
+    #[test]
+    fn ls_long_format() {
+        let transcript = "$ cd /\n\
+            $ ls -la\n\
+            total 12\n\
+            drwxr-xr-x 3 user group 4096 Jan  1 00:00 .\n\
+            drwxr-xr-x 3 user group 4096 Jan  1 00:00 ..\n\
+            -rw-r--r-- 1 user group 6442450944 Jan  1 00:00 big file.bin\n\
+            drwxr-xr-x 2 user group 4096 Jan  1 00:00 logs\n\
+            $ cd logs\n\
+            $ ls -l\n\
+            -rw-r--r-- 1 user group 2147483648 Feb 29 12:30 syslog";
+        let fs = FileSystem::replay(transcript.lines()).unwrap();
+
+        assert_eq!(fs.du("/logs"), Some(2147483648));
+        assert_eq!(fs.used_space(), 8589934592);
+        assert_eq!(
+            fs.find_files(|size| size > 4 << 30),
+            vec![("/big file.bin".to_string(), 6442450944)]
         );
-
-        Ok(format!(
-            "\nPart 1: {}\nPart 2: {}",
-            accumulator.to_string(),
-            size_heap.pop().unwrap().0
-        ))
-    } else {
-        panic!("No input file given!");
     }
-}
 
-fn parse_file(mut buf: BufReader<fs::File>) -> Tree {
-    let mut tree: Tree = Tree::new("/".to_string());
-
-    let root_ref: Rc<RefCell<Directory>> = tree.root.clone();
-
-    let mut current_dir = root_ref;
-
-    let mut line: String = String::new();
-    while let Ok(bytes_read) = buf.read_line(&mut line) {
-        if bytes_read > 0 {
-            let command_args = line.split_whitespace().map(|s| s).collect::<Vec<_>>();
-            if let Some(&s) = command_args.get(0) {
-                if s == "$" {
-                    if let Some(&command) = command_args.get(1) {
-                        match command {
-                            "cd" => {
-                                if let Some(&name) = command_args.get(2) {
-                                    match name {
-                                        "/" => {
-                                            current_dir = tree.root.clone();
-                                        }
-                                        ".." => {
-                                            if let Some(dir) =
-                                                &current_dir.clone().borrow().parent_directory
-                                            {
-                                                if let Some(parent_ref) = Weak::upgrade(dir) {
-                                                    current_dir = parent_ref.clone();
-                                                }
-                                            }
-                                        }
-                                        name @ _ => {
-                                            if let Some(dir) = current_dir
-                                                .clone()
-                                                .borrow()
-                                                .sub_directories
-                                                .iter()
-                                                .find(|&dir| dir.borrow().name == name.to_string())
-                                            {
-                                                current_dir = dir.clone();
-                                            };
-                                        }
-                                    }
-                                }
-                            }
-                            "ls" => loop {
-                                let mut ls_buf = String::new();
-                                if let Ok(bytes_read) = buf.read_line(&mut ls_buf) {
-                                    if bytes_read > 0 {
-                                        let args = ls_buf.split_whitespace().collect::<Vec<_>>();
-                                        if let Some(&s) = args.get(0) {
-                                            match s {
-                                                "$" => {
-                                                    buf.seek_relative(
-                                                        -(ls_buf.bytes().len() as i64),
-                                                    )
-                                                    .unwrap();
-                                                    break;
-                                                }
-                                                "dir" => {
-                                                    Tree::add_subdir(
-                                                        &current_dir,
-                                                        args.get(1).unwrap().to_string(),
-                                                    );
-                                                }
-                                                _ => {
-                                                    current_dir.borrow_mut().add_file(
-                                                        args.get(1).unwrap().to_string(),
-                                                        args.get(0)
-                                                            .unwrap()
-                                                            .parse::<i32>()
-                                                            .unwrap(),
-                                                    );
-                                                }
-                                            }
-                                        }
-                                    } else {
-                                        break;
-                                    }
-                                }
-                            },
-                            c @ _ => {
-                                panic!("Unkown command: {}", c)
-                            }
-                        };
-                    }
-                }
-            }
-            line.clear();
-        } else {
-            break;
+    #[test]
+    fn generated_transcripts() {
+        for seed in 0..20 {
+            let transcript = generate(seed, 300);
+            let fs = FileSystem::replay(transcript.lines()).unwrap();
+            let listed: u64 = transcript
+                .lines()
+                .filter_map(|line| line.split_once(' ')?.0.parse::<u64>().ok())
+                .sum();
+
+            assert_eq!(fs.used_space(), listed);
+            assert_eq!(fs.du("/"), Some(listed));
+            assert_eq!(fs.find_dirs(|_| true).len(), 301);
         }
     }
-
-    tree.total_used_space = tree.root.clone().borrow().size;
-
-    tree
 }
@@ -0,0 +1,267 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+
+pub const ROOT: &str = "/";
+
+/// Virtual filesystem reconstructed from a `$ cd` / `$ ls` terminal
+/// transcript. Directories are indexed by their absolute path.
+#[derive(Debug)]
+pub struct FileSystem {
+    directories: BTreeMap<String, Directory>,
+    current_dir: String,
+    used_space: u64,
+}
+
+#[derive(Debug, Default)]
+struct Directory {
+    files: BTreeMap<String, u64>,
+    sub_directories: BTreeSet<String>,
+}
+
+impl FileSystem {
+    pub fn new() -> Self {
+        let mut directories = BTreeMap::new();
+        directories.insert(ROOT.to_string(), Directory::default());
+
+        Self {
+            directories,
+            current_dir: ROOT.to_string(),
+            used_space: 0,
+        }
+    }
+
+    /// Replays a whole transcript. Listing a directory again overwrites the
+    /// entries it already had instead of adding them twice.
+    ///
+    /// Besides the puzzle's `<size> <name>` format, listings may be `ls -l`
+    /// style lines (`-rw-r--r-- 1 user group <size> <date> <name>`).
+    pub fn replay<'a>(lines: impl IntoIterator<Item = &'a str>) -> Result<Self, String> {
+        let mut fs = FileSystem::new();
+        let mut listing = false;
+
+        for line in lines {
+            let args = line.split_whitespace().collect::<Vec<_>>();
+            match args.as_slice() {
+                [] => {}
+                ["$", "cd", path] => {
+                    listing = false;
+                    fs.cd(path);
+                }
+                ["$", "ls", ..] => listing = true,
+                ["$", command, ..] => return Err(format!("Unknown command: {}", command)),
+                _ if !listing => return Err(format!("Output outside of ls: {}", line)),
+                ["total", _] => {}
+                ["dir", name] => fs.add_dir(name),
+                [size, name] => fs.add_file(name, parse_size(size, line)?)?,
+                [mode, _links, _owner, _group, size, _month, _day, _time, name @ ..]
+                    if !name.is_empty() =>
+                {
+                    let name = name.join(" ");
+                    match (mode.chars().next(), name.as_str()) {
+                        (_, "." | "..") => {}
+                        (Some('d'), _) => fs.add_dir(&name),
+                        (Some('-'), _) => fs.add_file(&name, parse_size(size, line)?)?,
+                        _ => {}
+                    }
+                }
+                _ => return Err(format!("Unexpected line: {}", line)),
+            }
+        }
+
+        Ok(fs)
+    }
+
+    /// Changes the current directory. Supports `/`, `..`, absolute and
+    /// relative paths. Unknown directories are created on the way.
+    pub fn cd(&mut self, path: &str) {
+        let mut target = if path.starts_with('/') {
+            ROOT.to_string()
+        } else {
+            self.current_dir.clone()
+        };
+
+        for component in path.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            target = if component == ".." {
+                parent_of(&target).to_string()
+            } else {
+                let child = join(&target, component);
+                self.ensure_dir(&target, &child);
+                child
+            };
+        }
+
+        self.current_dir = target;
+    }
+
+    pub fn add_dir(&mut self, name: &str) {
+        let parent = self.current_dir.clone();
+        let child = join(&parent, name);
+        self.ensure_dir(&parent, &child);
+    }
+
+    /// Adds or replaces a file in the current directory. Fails if the total
+    /// used space would no longer fit in a `u64`, which also guarantees that
+    /// no directory size can overflow.
+    pub fn add_file(&mut self, name: &str, size: u64) -> Result<(), String> {
+        let files = &mut self
+            .directories
+            .get_mut(&self.current_dir)
+            .expect("Current directory is always indexed")
+            .files;
+        let replaced = files.get(name).copied().unwrap_or(0);
+
+        self.used_space = (self.used_space - replaced)
+            .checked_add(size)
+            .ok_or_else(|| format!("Total size overflows with {} ({} bytes)", name, size))?;
+        files.insert(name.to_string(), size);
+
+        Ok(())
+    }
+
+    fn ensure_dir(&mut self, parent: &str, child: &str) {
+        if let Some(dir) = self.directories.get_mut(parent) {
+            dir.sub_directories.insert(child.to_string());
+        }
+        self.directories.entry(child.to_string()).or_default();
+    }
+
+    /// Absolute paths of the directories directly inside `path`.
+    pub fn sub_directories(&self, path: &str) -> impl Iterator<Item = &str> {
+        self.directories
+            .get(path)
+            .into_iter()
+            .flat_map(|dir| dir.sub_directories.iter().map(String::as_str))
+    }
+
+    /// Total size of every directory, keyed by absolute path.
+    pub fn dir_sizes(&self) -> BTreeMap<&str, u64> {
+        let mut sizes: BTreeMap<&str, u64> = BTreeMap::new();
+
+        // Children sort after their parents, so walking in reverse visits
+        // every sub directory before the directory containing it. No sum can
+        // exceed `used_space`, which `add_file` keeps from overflowing.
+        for (path, dir) in self.directories.iter().rev() {
+            let size = dir.files.values().sum::<u64>()
+                + dir
+                    .sub_directories
+                    .iter()
+                    .map(|sub| sizes[sub.as_str()])
+                    .sum::<u64>();
+            sizes.insert(path, size);
+        }
+
+        sizes
+    }
+
+    /// Like `du -s`: total size of the directory at `path`.
+    pub fn du(&self, path: &str) -> Option<u64> {
+        self.dir_sizes().get(normalize(path).as_str()).copied()
+    }
+
+    pub fn used_space(&self) -> u64 {
+        self.used_space
+    }
+
+    /// Directories whose total size satisfies `predicate`.
+    pub fn find_dirs(&self, predicate: impl Fn(u64) -> bool) -> Vec<(String, u64)> {
+        self.dir_sizes()
+            .into_iter()
+            .filter(|&(_, size)| predicate(size))
+            .map(|(path, size)| (path.to_string(), size))
+            .collect()
+    }
+
+    /// Files whose size satisfies `predicate`.
+    pub fn find_files(&self, predicate: impl Fn(u64) -> bool) -> Vec<(String, u64)> {
+        self.directories
+            .iter()
+            .flat_map(|(path, dir)| {
+                dir.files
+                    .iter()
+                    .map(move |(name, &size)| (join(path, name), size))
+            })
+            .filter(|&(_, size)| predicate(size))
+            .collect()
+    }
+
+    /// Renders the hierarchy in the same format as the puzzle description.
+    pub fn tree(&self) -> String {
+        let mut out = String::new();
+        self.render(ROOT, 0, &mut out);
+
+        out
+    }
+
+    fn render(&self, path: &str, depth: usize, out: &mut String) {
+        let dir = &self.directories[path];
+        writeln!(out, "{}- {} (dir)", "  ".repeat(depth), name_of(path)).unwrap();
+
+        let mut entries: Vec<(&str, Option<u64>)> = dir
+            .sub_directories
+            .iter()
+            .map(|sub| (name_of(sub), None))
+            .chain(
+                dir.files
+                    .iter()
+                    .map(|(name, &size)| (name.as_str(), Some(size))),
+            )
+            .collect();
+        entries.sort();
+
+        for (name, size) in entries {
+            match size {
+                Some(size) => writeln!(
+                    out,
+                    "{}- {} (file, size={})",
+                    "  ".repeat(depth + 1),
+                    name,
+                    size
+                )
+                .unwrap(),
+                None => self.render(&join(path, name), depth + 1, out),
+            }
+        }
+    }
+}
+
+fn parse_size(size: &str, line: &str) -> Result<u64, String> {
+    size.parse::<u64>()
+        .map_err(|_| format!("Invalid file size: {}", line))
+}
+
+fn join(parent: &str, name: &str) -> String {
+    if parent == ROOT {
+        format!("/{}", name)
+    } else {
+        format!("{}/{}", parent, name)
+    }
+}
+
+fn parent_of(path: &str) -> &str {
+    match path.rfind('/') {
+        Some(0) | None => ROOT,
+        Some(ix) => &path[..ix],
+    }
+}
+
+fn name_of(path: &str) -> &str {
+    if path == ROOT {
+        ROOT
+    } else {
+        &path[path.rfind('/').map_or(0, |ix| ix + 1)..]
+    }
+}
+
+/// Absolute form of `path` without `.`, `..` or repeated slashes.
+fn normalize(path: &str) -> String {
+    let mut normalized = ROOT.to_string();
+    for component in path.split('/').filter(|c| !c.is_empty() && *c != ".") {
+        normalized = if component == ".." {
+            parent_of(&normalized).to_string()
+        } else {
+            join(&normalized, component)
+        };
+    }
+
+    normalized
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use super::filesystem::{FileSystem, ROOT};
+
+pub enum PlanMode {
+    /// The single smallest directory that frees enough space.
+    Smallest,
+    /// The fewest non-nested directories that together free enough space.
+    /// Among sets of that size, the one freeing the most space is chosen.
+    MinimalSet,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub directories: Vec<(String, u64)>,
+    pub freed: u64,
+}
+
+pub struct DeletionPlanner {
+    pub total_space: u64,
+    pub target_free_space: u64,
+}
+
+impl DeletionPlanner {
+    /// Space that still has to be freed to reach the target.
+    pub fn space_needed(&self, fs: &FileSystem) -> u64 {
+        let free_space = self.total_space.saturating_sub(fs.used_space());
+        self.target_free_space.saturating_sub(free_space)
+    }
+
+    /// Directories to delete, or `None` if the target can't be reached. The
+    /// root directory itself is never proposed.
+    pub fn plan(&self, fs: &FileSystem, mode: PlanMode) -> Option<Plan> {
+        let needed = self.space_needed(fs);
+        if needed == 0 {
+            return Some(Plan {
+                directories: vec![],
+                freed: 0,
+            });
+        }
+
+        match mode {
+            PlanMode::Smallest => fs
+                .find_dirs(|size| size >= needed)
+                .into_iter()
+                .filter(|(path, _)| path != ROOT)
+                .min_by_key(|&(_, size)| size)
+                .map(|(path, size)| Plan {
+                    directories: vec![(path, size)],
+                    freed: size,
+                }),
+            PlanMode::MinimalSet => {
+                let sizes = fs.dir_sizes();
+                let best = best_per_count(fs, &sizes, ROOT, false);
+
+                best.into_iter()
+                    .find(|plan| plan.freed >= needed)
+                    .map(|mut plan| {
+                        plan.directories.sort();
+                        plan
+                    })
+            }
+        }
+    }
+}
+
+/// For every amount of non-nested directories `k` inside `path`, the
+/// selection of `k` directories freeing the most space. Index `k` of the
+/// result holds the plan for `k` directories. Non-nested directories never
+/// add up to more than the used space, so the sums can't overflow.
+fn best_per_count(
+    fs: &FileSystem,
+    sizes: &BTreeMap<&str, u64>,
+    path: &str,
+    selectable: bool,
+) -> Vec<Plan> {
+    let mut best = vec![Plan {
+        directories: vec![],
+        freed: 0,
+    }];
+
+    for sub in fs.sub_directories(path) {
+        let sub_best = best_per_count(fs, sizes, sub, true);
+        let mut merged: Vec<Option<Plan>> =
+            (0..best.len() + sub_best.len() - 1).map(|_| None).collect();
+
+        for (i, left) in best.iter().enumerate() {
+            for (j, right) in sub_best.iter().enumerate() {
+                let freed = left.freed + right.freed;
+                if merged[i + j].as_ref().is_none_or(|plan| plan.freed < freed) {
+                    merged[i + j] = Some(Plan {
+                        directories: [left.directories.clone(), right.directories.clone()].concat(),
+                        freed,
+                    });
+                }
+            }
+        }
+
+        best = merged.into_iter().flatten().collect();
+    }
+
+    if selectable {
+        let size = sizes[path];
+        let whole = Plan {
+            directories: vec![(path.to_string(), size)],
+            freed: size,
+        };
+        match best.get_mut(1) {
+            Some(plan) if plan.freed >= size => {}
+            Some(plan) => *plan = whole,
+            None => best.push(whole),
+        }
+    }
+
+    best
+}
@@ -1,6 +1,13 @@
 use std::fs::File;
 use std::io;
 
+use crate::solution::heatmap::{Format, Layer};
+
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub heatmap: Option<Layer>,
+    pub heatmap_format: Format,
+    pub heatmap_path: Option<String>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
@@ -0,0 +1,14 @@
+use input_gen::Rng;
+
+/// A `size` x `size` grid of tree heights, uniform between 0 and 9.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        input.extend((0..size).map(|_| (b'0' + rng.below(10) as u8) as char));
+        input.push('\n');
+    }
+
+    input
+}
@@ -1,15 +1,22 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::BufReader;
 use std::{env, time::Instant};
 
 use configuration::Configuration;
+use solution::heatmap::{Format, Layer};
 
 fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
-    
+
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -18,21 +25,53 @@ fn main() {
 
     match result {
         Ok(r) => println!("Result: {}", r),
-        Err(e) => println!("Error: {}", e)
+        Err(e) => println!("Error: {}", e),
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `<input> [--heatmap visible | scenic] [--format ansi | pgm | ppm] [--out <path>]`.
+/// `--heatmap` draws a layer of the analysis, in the terminal by default;
+/// image formats are written to the `--out` path.
+/// `--generate <seed> <size>` prints a random `size` x `size` forest instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason)
-        };
-        Configuration { input_file_buffer: Some(BufReader::new(input_file.unwrap())) }
-    } else { 
-        Configuration { input_file_buffer: None }
+    let mut config = Configuration {
+        input_file_buffer: None,
+        heatmap: None,
+        heatmap_format: Format::Ansi,
+        heatmap_path: None,
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--heatmap" => match args.next().as_deref().and_then(Layer::from_arg) {
+                Some(layer) => config.heatmap = Some(layer),
+                None => panic!("Expected visible or scenic after --heatmap"),
+            },
+            "--format" => match args.next().as_deref().and_then(Format::from_arg) {
+                Some(format) => config.heatmap_format = format,
+                None => panic!("Expected ansi, pgm or ppm after --format"),
+            },
+            "--out" => match args.next() {
+                Some(path) => config.heatmap_path = Some(path),
+                None => panic!("Expected a path after --out"),
+            },
+            "--generate" => match input_gen::parse_args(&mut args) {
+                Some(generate) => config.generate = Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            },
+            _ => {
+                let input_file = match File::open(&arg) {
+                    Ok(f) => f,
+                    Err(reason) => panic!("Couldn't open file {:?}: {}", arg, reason),
+                };
+                config.input_file_buffer = Some(BufReader::new(input_file));
+            }
+        }
     }
+
+    config
 }
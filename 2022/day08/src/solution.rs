@@ -1,14 +1,43 @@
-use std::{io::{BufReader, BufRead}, fs};
-use ndarray::{Array, Ix2, Array2, ArrayView};
+use input_check::{Validator, Violation};
+use ndarray::{Array, Array2, ArrayView, Ix2};
+use std::fs;
+use std::io::{self, BufRead, Write};
 
 use crate::configuration::Configuration;
 
-pub fn run(config: Configuration) -> Result<String, String>{
-
-    if config.input_file_buffer.is_some() {
-        let forest = parse_file(config.input_file_buffer.unwrap());
-        let part_1 = count_visible_trees(&forest);
-        let part_2 = count_highest_scenic_score(&forest);
+use self::forest::ForestAnalysis;
+use self::heatmap::Format;
+
+mod forest;
+pub mod heatmap;
+
+pub fn run(config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = config.input_file_buffer {
+        let lines = input_buf
+            .lines()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Couldn't read line: {}", e))?;
+        invariants()
+            .validate(&lines)
+            .map_err(|report| report.to_string())?;
+
+        let forest = parse_file(lines.join("\n").as_bytes())?;
+        let analysis = ForestAnalysis::new(&forest);
+
+        let part_1 = analysis.visible_count();
+        let part_2 = analysis.best_tree().map_or(0, |(_, score)| score);
+
+        if let Some(layer) = config.heatmap {
+            let image = heatmap::render(&forest, &analysis, layer, config.heatmap_format);
+            match (&config.heatmap_path, config.heatmap_format) {
+                (Some(path), _) => fs::write(path, image)
+                    .map_err(|e| format!("Couldn't write heatmap to {}: {}", path, e))?,
+                (None, Format::Ansi) => io::stdout()
+                    .write_all(&image)
+                    .map_err(|e| format!("Couldn't print heatmap: {}", e))?,
+                (None, _) => return Err("Image heatmaps need an --out path".to_string()),
+            }
+        }
 
         Ok(format!("\nPart 1: {}\nPart 2: {}", part_1, part_2))
     } else {
@@ -16,166 +45,163 @@ pub fn run(config: Configuration) -> Result<String, String>{
     }
 }
 
-fn count_visible_trees(forest: &Array<u8, Ix2>) -> i32 {
-    let mut visible_trees = Array::<u8, _>::zeros(forest.raw_dim());
-
-    {
-        // E -> W
-        for i in 0..forest.nrows() {
-            let mut max: Option<u8> = None;
-            for k in 0..forest.ncols() {
-                if max.is_none() || forest[[i, k]] > max.unwrap() {
-                    max = Some(forest[[i, k]]);
-                    visible_trees[[i, k]] = 1;
-                }
+/// The forest is a non-empty rectangle of single digit heights.
+fn invariants() -> Validator<'static> {
+    Validator::new()
+        .invariant("at least one row", |lines| match lines.first() {
+            Some(row) if !row.is_empty() => Ok(()),
+            _ => Err(Violation::new("the forest is empty")),
+        })
+        .every_line("digit heights", 0, |line| {
+            match line.chars().find(|c| !c.is_ascii_digit()) {
+                Some(c) => Err(format!("{:?} isn't a height", c)),
+                None => Ok(()),
             }
-        }
-    }
+        })
+        .invariant("rectangular grid", |lines| {
+            let width = lines.first().map_or(0, String::len);
+            match lines.iter().position(|line| line.len() != width) {
+                Some(ix) => Err(Violation::at(
+                    ix + 1,
+                    format!("{} trees, the first row has {}", lines[ix].len(), width),
+                )),
+                None => Ok(()),
+            }
+        })
+}
 
-    {
-        // W -> E
-        let mut i = forest.nrows();
-        while i > 0 as usize{
-            let mut k = forest.ncols();
-            let mut max: Option<u8> = None;
-             while k > 0 as usize {
-                let ix_i = i - 1 as usize;
-                let ix_k = k - 1 as usize;
-                if max.is_none() || forest[[ix_i, ix_k]] > max.unwrap() {
-                    max = Some(forest[[ix_i, ix_k]]);
-                    visible_trees[[ix_i, ix_k]] = 1;
-                }
-                k -= 1 as usize;
-             }
-            i -= 1 as usize;
-        }
+fn parse_file(buf: impl BufRead) -> Result<Array<u8, Ix2>, String> {
+    let mut array: Option<Array<u8, Ix2>> = None;
+
+    for (ix, line) in buf.lines().enumerate() {
+        let line = line.map_err(|e| format!("Couldn't read line: {}", e))?;
+        let heights = line
+            .chars()
+            .map(|c| c.to_digit(10).map(|d| d as u8))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or_else(|| format!("Line {}: expected only digits", ix + 1))?;
+
+        array
+            .get_or_insert_with(|| Array2::zeros((0, heights.len())))
+            .push_row(ArrayView::from(heights.as_slice()))
+            .map_err(|e| format!("Line {}: error adding row: {}", ix + 1, e))?;
     }
 
-    {
-        // N -> S
-        for i in 0..forest.ncols() {
-            let mut max: Option<u8> = None;
-            for k in 0..forest.nrows() {
-                if max.is_none() || forest[[k, i]] > max.unwrap() {
-                    max = Some(forest[[k, i]]);
-                    visible_trees[[k, i]] = 1;
-                }
-            }
-        }
-    }
+    array.ok_or_else(|| "Empty forest".to_string())
+}
 
-    {
-        // S -> N
-    let mut i = forest.ncols();
-        while i > 0 as usize {
-            let mut k = forest.nrows();
-            let mut max: Option<u8> = None;
-            while k > 0 as usize {
-                let ix_i = i - 1;
-                let ix_k = k - 1;
-                if max.is_none() || forest[[ix_k, ix_i]] > max.unwrap() {
-                    max = Some(forest[[ix_k, ix_i]]);
-                    visible_trees[[ix_k, ix_i]] = 1;
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array, Array2, Ix2};
+
+    use crate::generator::generate;
+    use crate::solution::{
+        forest::ForestAnalysis,
+        heatmap::{self, Format, Layer},
+        invariants, parse_file,
+    };
+
+    type Step<'a> = Box<dyn Fn(usize) -> Option<(usize, usize)> + 'a>;
+
+    /// Walks in every direction from every tree.
+    fn naive_scenic_score(forest: &Array<u8, Ix2>, (i, k): (usize, usize)) -> u64 {
+        let height = forest[[i, k]];
+        let (rows, cols) = forest.dim();
+        let directions: [Step; 4] = [
+            Box::new(|d| k.checked_sub(d).map(|k| (i, k))),
+            Box::new(|d| Some((i, k + d)).filter(|&(_, k)| k < cols)),
+            Box::new(|d| i.checked_sub(d).map(|i| (i, k))),
+            Box::new(|d| Some((i + d, k)).filter(|&(i, _)| i < rows)),
+        ];
+
+        directions
+            .iter()
+            .map(|step| {
+                let mut count = 0;
+                let mut d = 1;
+                while let Some((i, k)) = step(d) {
+                    count += 1;
+                    if forest[[i, k]] >= height {
+                        break;
+                    }
+                    d += 1;
                 }
-                k -= 1;
-            }
-            i -= 1;
-        }
+                count
+            })
+            .product()
     }
-    visible_trees.fold(0, |acc, &t| { acc + t as i32})
-}
 
-fn count_highest_scenic_score(forest: &Array<u8, Ix2>) -> i32 {
-    let mut max: i32 = 0;
-    for i in 0..forest.nrows() {
-        for k in 0..forest.ncols() {
-            let current_tree = forest[[i, k]]; 
-            
-            let mut left_count: i32 = 0;
-            let mut right_count: i32 = 0;
-            let mut up_count: i32 = 0;
-            let mut down_count: i32 = 0;
-
-            
-            //to left
-            let mut ix_k = k.clone();
-            while ix_k > 0 {
-                ix_k -= 1;
-                if forest[[i, ix_k]] < current_tree { 
-                    left_count += 1;
-                } else {
-                    left_count += 1;
-                    break;
-                }
-            }
+    #[test]
+    fn example() {
+        let forest = parse_file(include_str!("../test.txt").as_bytes()).unwrap();
+        let analysis = ForestAnalysis::new(&forest);
 
-            //to right
-            let mut ix_k = k.clone();
-            while ix_k < forest.ncols() - 1 {
-                ix_k += 1;
-                if forest[[i, ix_k]] < current_tree { 
-                    right_count += 1;
-                } else {
-                    right_count += 1;
-                    break;
-                }
-            }
+        assert_eq!(analysis.visible_count(), 21);
+        assert_eq!(analysis.best_tree(), Some(((3, 2), 8)));
+        assert_eq!(analysis.scenic_scores[[1, 2]], 4);
+        assert_eq!(analysis.visible_trees[[1, 3]], 0);
+    }
 
-            //up
-            let mut ix_i = i.clone();
-            while ix_i > 0 {
-                ix_i -= 1;
-                if forest[[ix_i, k]] < current_tree { 
-                    up_count += 1;
-                } else {
-                    up_count += 1;
-                    break;
-                }
-            }
+    #[test]
+    fn matches_naive_scenic_scores() {
+        let mut seed = 7u32;
+        let forest = Array2::from_shape_fn((17, 23), |_| {
+            seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+            (seed >> 16) as u8 % 10
+        });
+        let analysis = ForestAnalysis::new(&forest);
+
+        for (position, &score) in analysis.scenic_scores.indexed_iter() {
+            assert_eq!(score, naive_scenic_score(&forest, position));
+        }
+    }
 
-            //down
-            let mut ix_i = i.clone();
-            while ix_i < forest.nrows() - 1 {
-                ix_i += 1;
-                if forest[[ix_i, k]] < current_tree { 
-                    down_count += 1;
-                } else {
-                    down_count += 1;
-                    break;
-                }
+    #[test]
+    fn generated_forests() {
+        for (seed, size) in (0..10).zip([1, 2, 5, 20, 40, 99, 3, 60, 8, 31]) {
+            let input = generate(seed, size);
+            let lines = input.lines().map(String::from).collect::<Vec<_>>();
+            let forest = parse_file(input.as_bytes()).unwrap();
+            let analysis = ForestAnalysis::new(&forest);
+
+            assert!(invariants().validate(&lines).is_ok());
+            assert_eq!(forest.dim(), (size, size));
+            for (position, &score) in analysis.scenic_scores.indexed_iter() {
+                assert_eq!(score, naive_scenic_score(&forest, position));
             }
-
-            
-            let current_count = left_count * right_count * up_count * down_count;
-
-            if current_count > max { max = current_count }
         }
     }
 
-    max
-}
+    #[test]
+    fn ragged_forest() {
+        assert!(parse_file("123\n12".as_bytes()).is_err());
+        assert!(parse_file("1a3".as_bytes()).is_err());
 
-fn parse_file(buf: BufReader<fs::File>) -> Array<u8, Ix2> {
-    let mut iterator = buf.lines().peekable();
-
-    let width: usize;
-    if let Some(Ok(line)) = iterator.peek() {
-        width = line.len();
-    } else {
-        panic!("Couldn't parse line");
+        let lines = ["123", "1a3", "12"].map(String::from);
+        let report = invariants().validate(&lines).unwrap_err().to_string();
+        assert!(report.contains("digit heights (line 2): 'a' isn't a height"));
+        assert!(report.contains("rectangular grid (line 3): 2 trees, the first row has 3"));
     }
 
-    let mut array: Array<u8, Ix2> = Array2::zeros((0,width));
-    
-    for result in iterator {
-        if let Ok(line) = result {
-            let chars = line.chars().map(|c| c.to_string().parse::<u8>().unwrap()).collect::<Vec<_>>();
-            match array.push_row(ArrayView::from(chars.as_slice())) {
-                Ok(()) => (),
-                Err(error) => panic!("Error adding row: {}", error),
-            }
-        }
+    #[test]
+    fn heatmap_highlights_best_tree() {
+        let forest = parse_file(include_str!("../test.txt").as_bytes()).unwrap();
+        let analysis = ForestAnalysis::new(&forest);
+
+        let header = b"P5\n5 5\n255\n";
+        let pgm = heatmap::render(&forest, &analysis, Layer::Visibility, Format::Pgm);
+        assert_eq!(&pgm[..header.len()], header);
+        let pixels = &pgm[header.len()..];
+        assert_eq!(pixels.len(), 25);
+        assert_eq!(pixels.iter().filter(|&&p| p == 0).count(), 1);
+        assert_eq!(pixels[3 * 5 + 2], 0);
+        assert_eq!(pixels.iter().filter(|&&p| p == 255).count(), 20);
+
+        let scenic = heatmap::intensities(&analysis, Layer::Scenic);
+        assert_eq!(scenic[[3, 2]], 255);
+        assert_eq!(scenic[[0, 0]], 0);
+
+        let ppm = heatmap::render(&forest, &analysis, Layer::Scenic, Format::Ppm);
+        assert_eq!(ppm.len(), b"P6\n5 5\n255\n".len() + 25 * 3);
     }
-
-    array
-}
\ No newline at end of file
+}
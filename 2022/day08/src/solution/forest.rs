@@ -0,0 +1,80 @@
+use ndarray::{s, Array, Array2, ArrayView1, ArrayViewMut1, Axis, Ix2};
+
+/// Visibility mask (1 if the tree can be seen from outside the forest) and
+/// scenic score of every tree.
+pub struct ForestAnalysis {
+    pub visible_trees: Array<u8, Ix2>,
+    pub scenic_scores: Array<u64, Ix2>,
+}
+
+impl ForestAnalysis {
+    /// Sweeps every row and column once in each direction, so the whole
+    /// analysis is linear in the amount of trees.
+    pub fn new(forest: &Array<u8, Ix2>) -> Self {
+        let mut visible_trees = Array2::<u8>::zeros(forest.raw_dim());
+        let mut scenic_scores = Array2::<u64>::ones(forest.raw_dim());
+
+        // Rows, then columns
+        for axis in [Axis(1), Axis(0)] {
+            for ((line, mut visible), mut scenic) in forest
+                .lanes(axis)
+                .into_iter()
+                .zip(visible_trees.lanes_mut(axis))
+                .zip(scenic_scores.lanes_mut(axis))
+            {
+                // W -> E / N -> S
+                sweep(line, visible.view_mut(), scenic.view_mut());
+                // E -> W / S -> N
+                sweep(
+                    line.slice(s![..;-1]),
+                    visible.slice_mut(s![..;-1]),
+                    scenic.slice_mut(s![..;-1]),
+                );
+            }
+        }
+
+        Self {
+            visible_trees,
+            scenic_scores,
+        }
+    }
+
+    pub fn visible_count(&self) -> usize {
+        self.visible_trees.iter().filter(|&&v| v == 1).count()
+    }
+
+    /// Position and score of the tree with the highest scenic score.
+    pub fn best_tree(&self) -> Option<((usize, usize), u64)> {
+        self.scenic_scores
+            .indexed_iter()
+            .max_by_key(|&(_, &score)| score)
+            .map(|(position, &score)| (position, score))
+    }
+}
+
+/// Looks back along `line` from every tree. A monotonic stack keeps the
+/// indices of the trees that can still block the view, tallest first. Trees
+/// shorter than the current one can't block anything further on, so they are
+/// popped; whatever is left on top is the first tree blocking the view.
+fn sweep(line: ArrayView1<u8>, mut visible: ArrayViewMut1<u8>, mut scenic: ArrayViewMut1<u64>) {
+    let mut blockers: Vec<usize> = Vec::with_capacity(line.len());
+
+    for (ix, &height) in line.iter().enumerate() {
+        while let Some(&top) = blockers.last() {
+            if line[top] < height {
+                blockers.pop();
+            } else {
+                break;
+            }
+        }
+
+        match blockers.last() {
+            Some(&blocker) => scenic[ix] *= (ix - blocker) as u64,
+            None => {
+                visible[ix] = 1;
+                scenic[ix] *= ix as u64;
+            }
+        }
+        blockers.push(ix);
+    }
+}
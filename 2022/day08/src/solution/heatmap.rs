@@ -0,0 +1,129 @@
+use std::fmt::Write;
+
+use ndarray::{Array, Ix2};
+
+use super::forest::ForestAnalysis;
+
+/// Which matrix of the analysis is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    Visibility,
+    Scenic,
+}
+
+impl Layer {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "visible" => Some(Layer::Visibility),
+            "scenic" => Some(Layer::Scenic),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Binary greyscale image, the best tree is the only black pixel.
+    Pgm,
+    /// Binary colour image, blue (low) to yellow (high), best tree in red.
+    Ppm,
+    /// Terminal heatmap using 24-bit background colours, one tree height per
+    /// cell, best tree in red.
+    Ansi,
+}
+
+impl Format {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "pgm" => Some(Format::Pgm),
+            "ppm" => Some(Format::Ppm),
+            "ansi" => Some(Format::Ansi),
+            _ => None,
+        }
+    }
+}
+
+const BEST_TREE_COLOUR: [u8; 3] = [255, 0, 0];
+
+/// Scales the selected layer to 0..=255. Scenic scores span several orders of
+/// magnitude, so they are scaled logarithmically.
+pub fn intensities(analysis: &ForestAnalysis, layer: Layer) -> Array<u8, Ix2> {
+    match layer {
+        Layer::Visibility => analysis.visible_trees.mapv(|v| v * 255),
+        Layer::Scenic => {
+            let max = analysis.scenic_scores.iter().copied().max().unwrap_or(0);
+            let scale = ((max + 1) as f64).ln();
+            analysis.scenic_scores.mapv(|score| {
+                if scale == 0.0 {
+                    0
+                } else {
+                    (((score + 1) as f64).ln() / scale * 255.0).round() as u8
+                }
+            })
+        }
+    }
+}
+
+fn heat_colour(intensity: u8) -> [u8; 3] {
+    [intensity, intensity, 255 - intensity]
+}
+
+pub fn render(
+    forest: &Array<u8, Ix2>,
+    analysis: &ForestAnalysis,
+    layer: Layer,
+    format: Format,
+) -> Vec<u8> {
+    let intensities = intensities(analysis, layer);
+    let best = analysis.best_tree().map(|(position, _)| position);
+    let (rows, cols) = intensities.dim();
+
+    match format {
+        Format::Pgm => {
+            let mut out = format!("P5\n{} {}\n255\n", cols, rows).into_bytes();
+            // Keep every other tree above black so the best one stands out
+            out.extend(intensities.indexed_iter().map(|(position, &v)| {
+                if Some(position) == best {
+                    0
+                } else {
+                    v.max(1)
+                }
+            }));
+            out
+        }
+        Format::Ppm => {
+            let mut out = format!("P6\n{} {}\n255\n", cols, rows).into_bytes();
+            for (position, &v) in intensities.indexed_iter() {
+                if Some(position) == best {
+                    out.extend(BEST_TREE_COLOUR);
+                } else {
+                    out.extend(heat_colour(v));
+                }
+            }
+            out
+        }
+        Format::Ansi => {
+            let mut out = String::new();
+            for (i, row) in intensities.outer_iter().enumerate() {
+                for (k, &v) in row.iter().enumerate() {
+                    let [r, g, b] = if best == Some((i, k)) {
+                        BEST_TREE_COLOUR
+                    } else {
+                        heat_colour(v)
+                    };
+                    write!(
+                        out,
+                        "\x1b[48;2;{};{};{}m\x1b[30m{}",
+                        r,
+                        g,
+                        b,
+                        forest[[i, k]]
+                    )
+                    .unwrap();
+                }
+                out.push_str("\x1b[0m\n");
+            }
+            out.into_bytes()
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! The rope simulation as it was before knots moved to a `Vec`: a linked
+//! list of `Rc<RefCell<Knot>>`, one rope per knot count, and visited
+//! positions counted under formatted string keys. Kept for the benchmarks
+//! only, so the speedup can be measured on the same inputs.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use day_09::solution::movements::movement::Movement;
+
+pub type KnotLink = Rc<RefCell<Knot>>;
+
+#[derive(Debug, Default)]
+pub struct Knot {
+    pub x_pos: i32,
+    pub y_pos: i32,
+    pub next: Option<KnotLink>,
+}
+
+impl Knot {
+    fn key(&self) -> String {
+        format!("x: {}, y: {}", self.x_pos, self.y_pos)
+    }
+}
+
+pub struct RopeSimulation {
+    pub head: KnotLink,
+    pub tail: KnotLink,
+    pub positions_tail_visited: HashMap<String, usize>,
+}
+
+impl RopeSimulation {
+    pub fn new_with_knots(n_knots: usize) -> Self {
+        let head: KnotLink = Rc::default();
+        let mut tail = head.clone();
+        for _ in 1..n_knots {
+            let next_knot_link: KnotLink = Rc::default();
+            tail.borrow_mut().next = Some(next_knot_link.clone());
+            tail = next_knot_link;
+        }
+
+        let positions_tail_visited = HashMap::from([(tail.borrow().key(), 1)]);
+        Self {
+            head,
+            tail,
+            positions_tail_visited,
+        }
+    }
+
+    pub fn mark_tail_position(&mut self) {
+        let key = self.tail.borrow().key();
+        if let Some(count) = self.positions_tail_visited.get_mut(&key) {
+            *count += 1;
+        } else {
+            self.positions_tail_visited.insert(key, 1);
+        }
+    }
+
+    pub fn move_rope(&mut self, movement: &Movement) {
+        // Go one step at a time, avoid moving subsequent knots if not needed.
+        for _ in 0..movement.steps {
+            {
+                let mut head = self.head.borrow_mut();
+                head.x_pos += movement.direction.dx;
+                head.y_pos += movement.direction.dy;
+            }
+            self.affect_next_knot(&self.head);
+            self.mark_tail_position();
+        }
+    }
+
+    fn affect_next_knot(&self, current_link: &KnotLink) {
+        let current = current_link.borrow();
+
+        if let Some(next_link) = &current.next {
+            let should_affect_next = {
+                let mut next = next_link.borrow_mut();
+                let (dx, dy) = (current.x_pos - next.x_pos, current.y_pos - next.y_pos);
+                if dx.abs() > 1 || dy.abs() > 1 {
+                    next.x_pos += dx.signum();
+                    next.y_pos += dy.signum();
+                    true
+                } else {
+                    false
+                }
+            };
+
+            if should_affect_next {
+                self.affect_next_knot(next_link);
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+mod baseline;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use day_09::generator::generate;
+use day_09::solution::{
+    movements::movement::Movement, simulations::rope_simulation::RopeSimulation,
+};
+
+fn parse(input: &str) -> Vec<Movement> {
+    input
+        .lines()
+        .map(|line| line.parse::<Movement>().unwrap())
+        .collect()
+}
+
+fn simulate(mut rope: RopeSimulation, movements: &[Movement]) -> RopeSimulation {
+    for movement in movements {
+        rope.move_rope(movement);
+    }
+    rope
+}
+
+fn simulate_baseline(n_knots: usize, movements: &[Movement]) -> baseline::RopeSimulation {
+    let mut rope = baseline::RopeSimulation::new_with_knots(n_knots);
+    for movement in movements {
+        rope.move_rope(movement);
+    }
+    rope
+}
+
+/// The linked list simulation against the `Vec` one, 10 knots each.
+fn speedup(c: &mut Criterion) {
+    let inputs = [
+        ("input.txt", parse(include_str!("../../input.txt"))),
+        ("10k generated moves", parse(&generate(9, 10_000))),
+    ];
+    let mut group = c.benchmark_group("10 knots");
+
+    for (name, movements) in &inputs {
+        assert_eq!(
+            simulate_baseline(10, movements)
+                .positions_tail_visited
+                .len(),
+            simulate(RopeSimulation::new_with_knots(10), movements).positions_tail_visited(),
+            "The simulations disagree on {}",
+            name
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("Rc<RefCell<Knot>> list", name),
+            movements,
+            |b, movements| b.iter(|| simulate_baseline(10, black_box(movements))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("Vec<KnotPosition>", name),
+            movements,
+            |b, movements| {
+                b.iter(|| simulate(RopeSimulation::new_with_knots(10), black_box(movements)))
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn rope(c: &mut Criterion) {
+    let movements = parse(include_str!("../../input.txt"));
+
+    c.bench_function("2 and 10 knots, separate ropes", |b| {
+        b.iter(|| {
+            (
+                simulate(RopeSimulation::new_with_knots(2), black_box(&movements)),
+                simulate(RopeSimulation::new_with_knots(10), black_box(&movements)),
+            )
+        })
+    });
+    c.bench_function("2 and 10 knots, single pass", |b| {
+        b.iter(|| simulate(RopeSimulation::tracking(10, &[1, 9]), black_box(&movements)))
+    });
+
+    let generated = parse(&generate(9, 100_000));
+    c.bench_function("2 and 10 knots, single pass, 100k generated moves", |b| {
+        b.iter(|| simulate(RopeSimulation::tracking(10, &[1, 9]), black_box(&generated)))
+    });
+}
+
+criterion_group!(benches, speedup, rope);
+criterion_main!(benches);
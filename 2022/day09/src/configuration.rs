@@ -1,6 +1,32 @@
 use std::fs::File;
 use std::io;
 
+use crate::solution::knots::follow_rule::FollowRule;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FrameFormat {
+    Text,
+    Ppm,
+}
+
+impl FrameFormat {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "text" => Some(FrameFormat::Text),
+            "ppm" => Some(FrameFormat::Ppm),
+            _ => None,
+        }
+    }
+}
+
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub knots: usize,
+    pub follow: FollowRule,
+    pub show: bool,
+    pub stats: bool,
+    pub frames_path: Option<String>,
+    pub frame_format: FrameFormat,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
@@ -0,0 +1,16 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+/// `size` moves of 1 to 20 steps in the four puzzle directions.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let direction = rng.pick(&['R', 'L', 'U', 'D']);
+        writeln!(input, "{} {}", direction, rng.between(1..=20)).unwrap();
+    }
+
+    input
+}
@@ -1,3 +0,0 @@
-pub mod knot;
-pub mod knot_position;
-
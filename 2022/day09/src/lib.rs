@@ -0,0 +1,3 @@
+pub mod configuration;
+pub mod generator;
+pub mod solution;
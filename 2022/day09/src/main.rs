@@ -1,15 +1,21 @@
-mod configuration;
-mod solution;
 use std::fs::File;
 use std::io::BufReader;
 use std::{env, time::Instant};
 
-use configuration::Configuration;
+use day_09::configuration::{self, Configuration, FrameFormat};
+use day_09::generator;
+use day_09::solution;
+use day_09::solution::knots::follow_rule::FollowRule;
 
 fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
 
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     let now = Instant::now();
 
     let result = solution::run(config);
@@ -21,22 +27,63 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `<input> [--follow chebyshev | manhattan] [--knots N] [--show] [--stats]
+/// [--frames <path>] [--frame-format text | ppm]`. `--follow` picks how knots
+/// are pulled along, the puzzle's Chebyshev rule by default. `--show` draws
+/// the final rope of `--knots` knots (10 by default) and the positions its
+/// tail visited. `--frames` writes the rope after every step, as a single
+/// text file or as a directory of PPM images. `--stats` reports how often
+/// every knot visited each position.
+/// `--generate <seed> <size>` prints `size` random moves instead.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason),
-        };
-        Configuration {
-            input_file_buffer: Some(BufReader::new(input_file.unwrap())),
-        }
-    } else {
-        Configuration {
-            input_file_buffer: None,
+    let mut config = Configuration {
+        input_file_buffer: None,
+        knots: 10,
+        follow: FollowRule::Chebyshev,
+        show: false,
+        stats: false,
+        frames_path: None,
+        frame_format: FrameFormat::Text,
+        generate: None,
+    };
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--knots" => match args.next().map(|n| n.parse::<usize>()) {
+                Some(Ok(n)) if n >= 2 => config.knots = n,
+                _ => panic!("Expected at least 2 knots after --knots"),
+            },
+            "--follow" => match args.next().as_deref().and_then(FollowRule::from_arg) {
+                Some(rule) => config.follow = rule,
+                None => panic!("Expected chebyshev or manhattan after --follow"),
+            },
+            "--show" => config.show = true,
+            "--stats" => config.stats = true,
+            "--frames" => match args.next() {
+                Some(path) => config.frames_path = Some(path),
+                None => panic!("Expected a path after --frames"),
+            },
+            "--frame-format" => match args.next().as_deref().and_then(FrameFormat::from_arg) {
+                Some(format) => config.frame_format = format,
+                None => panic!("Expected text or ppm after --frame-format"),
+            },
+            "--generate" => match input_gen::parse_args(&mut args) {
+                Some(generate) => config.generate = Some(generate),
+                None => panic!("Expected a seed and a size after --generate"),
+            },
+            _ => {
+                let input_file = match File::open(&arg) {
+                    Ok(f) => f,
+                    Err(reason) => panic!("Couldn't open file {:?}: {}", arg, reason),
+                };
+                config.input_file_buffer = Some(BufReader::new(input_file));
+            }
         }
     }
+
+    config
 }
@@ -1,27 +1,285 @@
-mod knots;
-mod movements;
-mod simulations;
+pub mod knots;
+pub mod movements;
+pub mod renderers;
+pub mod simulations;
 
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::fs;
 use std::io::BufRead;
+use std::path::Path;
 
-use crate::configuration::Configuration;
+use crate::configuration::{Configuration, FrameFormat};
 
-use self::{movements::movement::Movement, simulations::rope_simulation::RopeSimulation};
+use self::{
+    movements::movement::Movement,
+    renderers::rope_renderer::{self, Bounds},
+    simulations::rope_simulation::RopeSimulation,
+};
 
-pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        let mut bridge = RopeSimulation::new_with_knots(10);
+/// Knot 1 of a 10 knot rope moves exactly like the tail of a 2 knot rope,
+/// so both parts come from a single simulation.
+pub fn run(mut config: Configuration) -> Result<String, String> {
+    if let Some(input_buf) = &mut config.input_file_buffer {
+        let movements = input_buf
+            .lines()
+            .map(|line| {
+                line.map_err(|e| format!("Couldn't read line: {}", e))?
+                    .parse::<Movement>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok(s) = line {
-                bridge.move_rope(Movement::from_string(s));
-            }
+        let mut bridge = RopeSimulation::tracking(10, &[1, 9]).with_rule(config.follow);
+        for movement in &movements {
+            bridge.move_rope(movement);
+        }
+
+        if config.show || config.frames_path.is_some() {
+            render(&config, &movements)?;
         }
 
-        //dbg!(&bridge.positions_tail_visited);
+        let mut result = format!(
+            "Part 1: {}, Part 2: {}",
+            bridge.positions_visited_by(1).unwrap(),
+            bridge.positions_visited_by(9).unwrap()
+        );
+        if config.stats {
+            write!(result, "\n{}", report(&config, &movements).trim_end()).unwrap();
+        }
 
-        Ok(bridge.positions_tail_visited.len().to_string())
+        Ok(result)
     } else {
         panic!("No file provided!");
     }
 }
+
+const MOST_VISITED: usize = 3;
+
+/// Visits of every knot of a `--knots` rope, which covers the tails of every
+/// shorter rope too.
+fn report(config: &Configuration, movements: &[Movement]) -> String {
+    let mut rope = RopeSimulation::tracking_all(config.knots).with_rule(config.follow);
+    for movement in movements {
+        rope.move_rope(movement);
+    }
+    let mut out = String::new();
+
+    writeln!(
+        out,
+        "Distinct positions visited by the tail, per rope length:"
+    )
+    .unwrap();
+    for (length, distinct) in rope.distinct_by_rope_length() {
+        writeln!(out, "{:>4} knots: {}", length, distinct).unwrap();
+    }
+
+    for knot in 0..config.knots {
+        let stats = rope.stats(knot).unwrap();
+        let (low, high) = stats.bounding_box();
+        let most_visited = stats
+            .most_visited(MOST_VISITED)
+            .iter()
+            .map(|((x, y, z), count)| format!("({}, {}, {}) x{}", x, y, z, count))
+            .collect::<Vec<_>>();
+        writeln!(
+            out,
+            "Knot {}: {} distinct positions over {} steps, from ({}) to ({}), most visited {}",
+            knot,
+            stats.distinct(),
+            stats.total() - 1,
+            low,
+            high,
+            most_visited.join(", ")
+        )
+        .unwrap();
+    }
+
+    out
+}
+
+const PPM_SCALE: usize = 8;
+
+fn render(config: &Configuration, movements: &[Movement]) -> Result<(), String> {
+    let mut rope = RopeSimulation::new_with_knots(config.knots).with_rule(config.follow);
+    let frames = rope_renderer::record(&mut rope, movements);
+    let bounds = Bounds::around(frames.iter().flat_map(|frame| &frame.knots));
+    let tail = config.knots - 1;
+
+    if config.show {
+        let last = frames.last().map_or(rope.knots.as_slice(), |f| &f.knots);
+        println!("{}", rope_renderer::draw_rope(last, &bounds));
+        println!(
+            "{}",
+            rope_renderer::draw_visits(rope.stats(tail).unwrap().frequencies(), &bounds)
+        );
+    }
+
+    match (&config.frames_path, config.frame_format) {
+        (None, _) => {}
+        (Some(path), FrameFormat::Text) => {
+            fs::write(
+                path,
+                rope_renderer::draw_frames(movements, &frames, &bounds),
+            )
+            .map_err(|e| format!("Couldn't write frames to {}: {}", path, e))?;
+        }
+        (Some(path), FrameFormat::Ppm) => {
+            let dir = Path::new(path);
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Couldn't create directory {}: {}", path, e))?;
+
+            let mut trail = HashSet::from([(0, 0)]);
+            for (ix, frame) in frames.iter().enumerate() {
+                trail.insert(frame.knots[tail].plane_key());
+                let image = rope_renderer::draw_ppm(&frame.knots, &trail, &bounds, PPM_SCALE);
+                let file = dir.join(format!("frame_{:05}.ppm", ix + 1));
+                fs::write(&file, image)
+                    .map_err(|e| format!("Couldn't write frame {}: {}", file.display(), e))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{
+        knots::follow_rule::FollowRule,
+        movements::movement::Movement,
+        renderers::rope_renderer::{self, Bounds},
+        simulations::rope_simulation::RopeSimulation,
+    };
+
+    fn simulate(input: &str, n_knots: usize) -> RopeSimulation {
+        let mut rope = RopeSimulation::tracking(n_knots, &[1, n_knots - 1]);
+        for line in input.lines() {
+            rope.move_rope(&line.parse::<Movement>().unwrap());
+        }
+        rope
+    }
+
+    #[test]
+    fn examples() {
+        let rope = simulate(include_str!("../test.txt"), 10);
+        assert_eq!(rope.positions_visited_by(1), Some(13));
+        assert_eq!(rope.positions_tail_visited(), 1);
+
+        let rope = simulate(include_str!("../test2.txt"), 10);
+        assert_eq!(rope.positions_tail_visited(), 36);
+        assert_eq!(rope.positions_visited_by(5), None);
+    }
+
+    #[test]
+    fn single_pass_matches_separate_ropes() {
+        let input = include_str!("../test2.txt");
+        let combined = simulate(input, 10);
+
+        let mut short = RopeSimulation::new_with_knots(2);
+        for line in input.lines() {
+            short.move_rope(&line.parse::<Movement>().unwrap());
+        }
+
+        assert_eq!(
+            combined.positions_visited_by(1),
+            Some(short.positions_tail_visited())
+        );
+    }
+
+    #[test]
+    fn single_pass_matches_separate_ropes_on_generated_moves() {
+        for seed in 0..10 {
+            let input = generate(seed, 2000);
+            let combined = simulate(&input, 10);
+
+            for n_knots in [2, 10] {
+                let mut rope = RopeSimulation::new_with_knots(n_knots);
+                for line in input.lines() {
+                    rope.move_rope(&line.parse::<Movement>().unwrap());
+                }
+                assert_eq!(
+                    combined.positions_visited_by(n_knots - 1),
+                    Some(rope.positions_tail_visited())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn draws_puzzle_example() {
+        let movements = include_str!("../test.txt")
+            .lines()
+            .map(|line| line.parse::<Movement>().unwrap())
+            .collect::<Vec<_>>();
+        let mut rope = RopeSimulation::new_with_knots(2);
+        let frames = rope_renderer::record(&mut rope, &movements);
+        let bounds = Bounds::around(frames.iter().flat_map(|frame| &frame.knots));
+
+        assert_eq!(frames.len(), 24);
+        let text = rope_renderer::draw_frames(&movements, &frames, &bounds);
+        assert!(text.starts_with("== R 4 ==\n\n......\n......\n......\n......\nTH....\n\n"));
+        assert_eq!(
+            rope_renderer::draw_rope(&frames[23].knots, &bounds),
+            "......\n......\n.TH...\n......\ns.....\n"
+        );
+        assert_eq!(
+            rope_renderer::draw_visits(rope.stats(1).unwrap().frequencies(), &bounds),
+            "..##..\n...##.\n.####.\n....#.\ns###..\n"
+        );
+    }
+
+    #[test]
+    fn diagonal_and_3d_movements() {
+        let movement = "DL 3".parse::<Movement>().unwrap();
+        assert_eq!((movement.direction.dx, movement.direction.dy), (-1, -1));
+        assert_eq!(movement.to_string(), "DL 3");
+        assert_eq!("RU 1".parse::<Movement>().unwrap().to_string(), "UR 1");
+        assert!("RL 1".parse::<Movement>().is_err());
+        assert!("X 1".parse::<Movement>().is_err());
+        assert!("U".parse::<Movement>().is_err());
+
+        // A diagonal head drags the rope along the diagonal
+        let mut rope = RopeSimulation::new_with_knots(3);
+        rope.move_rope(&"URF 4".parse().unwrap());
+        assert_eq!(rope.knots[2].key(), (2, 2, 2));
+        assert_eq!(rope.positions_tail_visited(), 3);
+    }
+
+    #[test]
+    fn manhattan_rule_keeps_knots_orthogonally_adjacent() {
+        let mut rope = RopeSimulation::new_with_knots(2).with_rule(FollowRule::Manhattan);
+        rope.move_rope(&"R 1".parse().unwrap());
+        assert_eq!(rope.knots[1].key(), (0, 0, 0));
+        rope.move_rope(&"U 1".parse().unwrap());
+        assert_eq!(rope.knots[1].key(), (1, 0, 0));
+        // The tail needs two steps to catch up, going through (1, 1)
+        rope.move_rope(&"UR 1".parse().unwrap());
+        assert_eq!(rope.knots[1].key(), (2, 1, 0));
+        assert_eq!(rope.stats(1).unwrap().frequency((1, 1, 0)), 1);
+        assert_eq!(rope.positions_tail_visited(), 4);
+    }
+
+    #[test]
+    fn visit_stats() {
+        let rope = simulate(include_str!("../test2.txt"), 10);
+        assert_eq!(rope.stats(9).unwrap().frequency((0, 0, 0)), 17);
+
+        let mut rope = RopeSimulation::tracking_all(10);
+        for line in include_str!("../test2.txt").lines() {
+            rope.move_rope(&line.parse().unwrap());
+        }
+        let by_length = rope.distinct_by_rope_length();
+        assert_eq!(by_length.len(), 9);
+        assert_eq!(by_length[0], (2, 88));
+        assert_eq!(by_length[8], (10, 36));
+
+        let rope = simulate(include_str!("../test.txt"), 2);
+        let tail = rope.stats(1).unwrap();
+        assert_eq!(tail.distinct(), 13);
+        assert_eq!(tail.total(), 25);
+        assert_eq!(tail.most_visited(1), vec![((4, 3, 0), 6)]);
+        let (low, high) = tail.bounding_box();
+        assert_eq!((low.key(), high.key()), ((0, 0, 0), (4, 4, 0)));
+    }
+}
@@ -1,2 +1,2 @@
-pub mod knot;
-pub mod knot_position;
\ No newline at end of file
+pub mod follow_rule;
+pub mod knot_position;
@@ -0,0 +1,63 @@
+use super::knot_position::KnotPosition;
+
+/// How far a knot can get from the one before it before being pulled along.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FollowRule {
+    /// Knots touch when they are adjacent, diagonals included, and catch up
+    /// with a single (possibly diagonal) step. The puzzle's rule.
+    #[default]
+    Chebyshev,
+    /// Knots only touch when adjacent along an axis, and catch up one axis
+    /// at a time, longest distance first.
+    Manhattan,
+}
+
+impl FollowRule {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "chebyshev" => Some(FollowRule::Chebyshev),
+            "manhattan" => Some(FollowRule::Manhattan),
+            _ => None,
+        }
+    }
+
+    pub fn distance(&self, (dx, dy, dz): (i32, i32, i32)) -> i32 {
+        match self {
+            FollowRule::Chebyshev => dx.abs().max(dy.abs()).max(dz.abs()),
+            FollowRule::Manhattan => dx.abs() + dy.abs() + dz.abs(),
+        }
+    }
+
+    pub fn touches(&self, knot: &KnotPosition, leader: &KnotPosition) -> bool {
+        self.distance(leader.distance_to(knot)) <= 1
+    }
+
+    /// Moves `knot` a single step towards `leader` unless they touch.
+    /// Returns whether it moved. Under the Manhattan rule a knot can need
+    /// several steps to catch up.
+    pub fn follow(&self, knot: &mut KnotPosition, leader: &KnotPosition) -> bool {
+        if self.touches(knot, leader) {
+            return false;
+        }
+
+        let (dx, dy, dz) = leader.distance_to(knot);
+        match self {
+            FollowRule::Chebyshev => {
+                knot.x_pos += dx.signum();
+                knot.y_pos += dy.signum();
+                knot.z_pos += dz.signum();
+            }
+            FollowRule::Manhattan => {
+                if dx.abs() >= dy.abs() && dx.abs() >= dz.abs() {
+                    knot.x_pos += dx.signum();
+                } else if dy.abs() >= dz.abs() {
+                    knot.y_pos += dy.signum();
+                } else {
+                    knot.z_pos += dz.signum();
+                }
+            }
+        }
+
+        true
+    }
+}
@@ -1,22 +1,45 @@
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+use std::fmt;
+
+use crate::solution::movements::direction::Direction;
+
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct KnotPosition {
     pub x_pos: i32,
     pub y_pos: i32,
+    pub z_pos: i32,
 }
 
 impl KnotPosition {
     pub fn new() -> Self {
-        Self { x_pos: 0, y_pos: 0 }
+        Self::default()
     }
 
-    pub fn distance_to(&self, other: &KnotPosition) -> (i32, i32) {
+    pub fn distance_to(&self, other: &KnotPosition) -> (i32, i32, i32) {
         let distance_x: i32 = self.x_pos - other.x_pos;
         let distance_y: i32 = self.y_pos - other.y_pos;
+        let distance_z: i32 = self.z_pos - other.z_pos;
 
-        (distance_x, distance_y)
+        (distance_x, distance_y, distance_z)
     }
 
-    pub fn to_string(&self) -> String {
-        format!("x: {}, y: {}", self.x_pos, self.y_pos)
+    pub fn step(&mut self, direction: &Direction) {
+        self.x_pos += direction.dx;
+        self.y_pos += direction.dy;
+        self.z_pos += direction.dz;
+    }
+
+    pub fn key(&self) -> (i32, i32, i32) {
+        (self.x_pos, self.y_pos, self.z_pos)
+    }
+
+    /// Position seen from above the `z` axis, as drawn by the renderers.
+    pub fn plane_key(&self) -> (i32, i32) {
+        (self.x_pos, self.y_pos)
+    }
+}
+
+impl fmt::Display for KnotPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "x: {}, y: {}, z: {}", self.x_pos, self.y_pos, self.z_pos)
     }
 }
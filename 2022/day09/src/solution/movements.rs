@@ -1,2 +1,2 @@
+pub mod direction;
 pub mod movement;
-pub mod direction;
\ No newline at end of file
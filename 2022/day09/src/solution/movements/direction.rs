@@ -1,5 +1,54 @@
-#[derive(Debug, PartialEq, Eq)]
-pub enum Direction {
-    Horizontal,
-    Vertical,
+/// Unit step along each axis. `x` grows to the right (`R`), `y` upwards
+/// (`U`) and `z` forwards (`F`), so `UR` is `(1, 1, 0)`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Direction {
+    pub dx: i32,
+    pub dy: i32,
+    pub dz: i32,
+}
+
+impl Direction {
+    /// Parses one or more of `R/L`, `U/D` and `F/B`, in any order, with at
+    /// most one letter per axis.
+    pub fn from_letters(s: &str) -> Result<Self, String> {
+        let mut direction = Direction::default();
+
+        for letter in s.chars() {
+            let (axis, unit) = match letter {
+                'R' => (&mut direction.dx, 1),
+                'L' => (&mut direction.dx, -1),
+                'U' => (&mut direction.dy, 1),
+                'D' => (&mut direction.dy, -1),
+                'F' => (&mut direction.dz, 1),
+                'B' => (&mut direction.dz, -1),
+                _ => return Err(format!("Unknown direction {:?} in {:?}", letter, s)),
+            };
+            if *axis != 0 {
+                return Err(format!("Direction {:?} moves twice along an axis", s));
+            }
+            *axis = unit;
+        }
+
+        if direction == Direction::default() {
+            Err("Missing direction".to_string())
+        } else {
+            Ok(direction)
+        }
+    }
+
+    /// Vertical letter first, as in `UR` or `DL`, then the depth one.
+    pub fn letters(&self) -> String {
+        [
+            (self.dy, 'U', 'D'),
+            (self.dx, 'R', 'L'),
+            (self.dz, 'F', 'B'),
+        ]
+        .iter()
+        .filter_map(|&(unit, positive, negative)| match unit {
+            1 => Some(positive),
+            -1 => Some(negative),
+            _ => None,
+        })
+        .collect()
+    }
 }
@@ -1,45 +1,36 @@
+use std::{fmt, str::FromStr};
+
 use super::direction::Direction;
 
-#[derive(Debug, PartialEq, Eq)]
+/// `<direction> <steps>`, the direction being any combination of letters
+/// accepted by `Direction::from_letters`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Movement {
-    pub amount: i32,
     pub direction: Direction,
+    pub steps: u32,
 }
 
-impl Movement {
-    pub fn from_string(s: String) -> Self {
-        let split_str: Vec<&str> = s.split_whitespace().collect::<Vec<_>>();
-        let amount_str: &str = split_str.get(1).unwrap();
-        let direction_str: &str = split_str.get(0).unwrap();
+impl FromStr for Movement {
+    type Err = String;
 
-        let parsed_abs_amount: i32 = amount_str.parse::<i32>().expect("Couldn't parse amount");
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction_str, steps_str) = s
+            .split_once(' ')
+            .ok_or_else(|| format!("Expected a direction and a distance in {:?}", s))?;
 
-        let (amount, direction): (i32, Direction) = if direction_str == "R" || direction_str == "L"
-        {
-            (
-                if direction_str == "R" {
-                    parsed_abs_amount
-                } else {
-                    -parsed_abs_amount
-                },
-                Direction::Horizontal,
-            )
-        } else if direction_str == "U" || direction_str == "D" {
-            (
-                if direction_str == "U" {
-                    parsed_abs_amount
-                } else {
-                    -parsed_abs_amount
-                },
-                Direction::Vertical,
-            )
-        } else {
-            panic!("Couldn't parse direction");
-        };
+        Ok(Movement {
+            direction: Direction::from_letters(direction_str)?,
+            steps: steps_str
+                .trim()
+                .parse::<u32>()
+                .map_err(|e| format!("Couldn't parse distance {:?}: {}", steps_str, e))?,
+        })
+    }
+}
 
-        Movement {
-            amount: amount,
-            direction: direction,
-        }
+/// Formats the movement as it appears in the input.
+impl fmt::Display for Movement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.direction.letters(), self.steps)
     }
 }
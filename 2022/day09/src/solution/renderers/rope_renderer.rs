@@ -0,0 +1,187 @@
+use std::{collections::HashSet, fmt::Write};
+
+use crate::solution::{
+    knots::knot_position::KnotPosition,
+    movements::movement::Movement,
+    simulations::{rope_simulation::RopeSimulation, visit_stats::Visits},
+};
+
+/// Inclusive area of the grid that gets drawn, the same for every frame so
+/// frames can be compared with each other. Ropes are drawn as seen from
+/// above the `z` axis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min_x: i32,
+    pub max_x: i32,
+    pub min_y: i32,
+    pub max_y: i32,
+}
+
+impl Bounds {
+    /// Smallest area holding the starting point and every given position.
+    pub fn around<'a>(positions: impl IntoIterator<Item = &'a KnotPosition>) -> Self {
+        let start = Self {
+            min_x: 0,
+            max_x: 0,
+            min_y: 0,
+            max_y: 0,
+        };
+
+        positions.into_iter().fold(start, |bounds, position| Self {
+            min_x: bounds.min_x.min(position.x_pos),
+            max_x: bounds.max_x.max(position.x_pos),
+            min_y: bounds.min_y.min(position.y_pos),
+            max_y: bounds.max_y.max(position.y_pos),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max_x - self.min_x + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max_y - self.min_y + 1) as usize
+    }
+
+    /// Calls `cell` for every position, row by row from the top left, as
+    /// the puzzle draws its grids (y grows upwards).
+    fn rows<T>(&self, cell: impl Fn((i32, i32)) -> T) -> Vec<Vec<T>> {
+        (self.min_y..=self.max_y)
+            .rev()
+            .map(|y| (self.min_x..=self.max_x).map(|x| cell((x, y))).collect())
+            .collect()
+    }
+}
+
+/// The knots after a single step, and the index of the movement the step
+/// belongs to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub movement: usize,
+    pub knots: Vec<KnotPosition>,
+}
+
+/// Runs `movements` on `simulation`, keeping a frame for every step.
+pub fn record(simulation: &mut RopeSimulation, movements: &[Movement]) -> Vec<Frame> {
+    let mut frames = vec![];
+
+    for (ix, movement) in movements.iter().enumerate() {
+        simulation.move_rope_with(movement, |knots| {
+            frames.push(Frame {
+                movement: ix,
+                knots: knots.to_vec(),
+            })
+        });
+    }
+
+    frames
+}
+
+/// Puzzle notation: `H` for the head, then the knot numbers. The tail of a
+/// two knot rope is `T`, as in the first part of the puzzle.
+pub fn knot_label(knot: usize, n_knots: usize) -> char {
+    match knot {
+        0 => 'H',
+        1 if n_knots == 2 => 'T',
+        _ => char::from_digit(knot as u32 % 36, 36).unwrap(),
+    }
+}
+
+/// Knot covering `position`. Knots closer to the head cover the ones
+/// behind them.
+fn knot_at(knots: &[KnotPosition], position: (i32, i32)) -> Option<usize> {
+    knots.iter().position(|knot| knot.plane_key() == position)
+}
+
+fn join_rows(rows: Vec<Vec<char>>) -> String {
+    let mut out = String::new();
+    for row in rows {
+        writeln!(out, "{}", row.into_iter().collect::<String>()).unwrap();
+    }
+    out
+}
+
+/// Draws the rope as the puzzle does, with `s` marking the start.
+pub fn draw_rope(knots: &[KnotPosition], bounds: &Bounds) -> String {
+    join_rows(bounds.rows(|position| match knot_at(knots, position) {
+        Some(knot) => knot_label(knot, knots.len()),
+        None if position == (0, 0) => 's',
+        None => '.',
+    }))
+}
+
+/// Marks every visited position with `#`, except the start which is `s`.
+pub fn draw_visits(visits: &Visits, bounds: &Bounds) -> String {
+    let visited: HashSet<(i32, i32)> = visits.keys().map(|&(x, y, _)| (x, y)).collect();
+
+    join_rows(bounds.rows(|position| {
+        if position == (0, 0) {
+            's'
+        } else if visited.contains(&position) {
+            '#'
+        } else {
+            '.'
+        }
+    }))
+}
+
+/// Every frame as text, grouped under an `== R 4 ==` header for each
+/// movement like in the puzzle examples.
+pub fn draw_frames(movements: &[Movement], frames: &[Frame], bounds: &Bounds) -> String {
+    let mut out = String::new();
+    let mut current_movement = None;
+
+    for frame in frames {
+        if current_movement != Some(frame.movement) {
+            current_movement = Some(frame.movement);
+            writeln!(out, "== {} ==\n", movements[frame.movement]).unwrap();
+        }
+        writeln!(out, "{}", draw_rope(&frame.knots, bounds)).unwrap();
+    }
+
+    out
+}
+
+const BACKGROUND: [u8; 3] = [16, 16, 16];
+const START: [u8; 3] = [0, 90, 255];
+const TRAIL: [u8; 3] = [90, 90, 90];
+const HEAD: [u8; 3] = [255, 0, 0];
+const TAIL: [u8; 3] = [0, 220, 0];
+
+/// Binary PPM image of a frame with `scale` pixels per cell. The positions
+/// the tail visited so far are drawn as a grey trail, knots fade from red
+/// (head) to yellow with a green tail.
+pub fn draw_ppm(
+    knots: &[KnotPosition],
+    trail: &HashSet<(i32, i32)>,
+    bounds: &Bounds,
+    scale: usize,
+) -> Vec<u8> {
+    let n_knots = knots.len();
+    let rows = bounds.rows(|position| match knot_at(knots, position) {
+        Some(0) => HEAD,
+        Some(knot) if knot == n_knots - 1 => TAIL,
+        Some(knot) => [255, (255 * knot / n_knots) as u8, 0],
+        None if position == (0, 0) => START,
+        None if trail.contains(&position) => TRAIL,
+        None => BACKGROUND,
+    });
+
+    let mut out = format!(
+        "P6\n{} {}\n255\n",
+        bounds.width() * scale,
+        bounds.height() * scale
+    )
+    .into_bytes();
+    for row in rows {
+        for _ in 0..scale {
+            for colour in &row {
+                for _ in 0..scale {
+                    out.extend(colour);
+                }
+            }
+        }
+    }
+
+    out
+}
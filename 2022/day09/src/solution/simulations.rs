@@ -1 +1,2 @@
-pub mod rope_simulation;
\ No newline at end of file
+pub mod rope_simulation;
+pub mod visit_stats;
@@ -1,101 +1,129 @@
 use std::collections::HashMap;
 
 use crate::solution::{
-    knots::{
-        knot::{Knot, KnotLink},
-        knot_position::KnotPosition,
-    },
-    movements::{direction::Direction, movement::Movement},
+    knots::{follow_rule::FollowRule, knot_position::KnotPosition},
+    movements::movement::Movement,
 };
 
-#[derive(Debug, PartialEq, Eq)]
+use super::visit_stats::{VisitStats, Visits};
+
+/// A rope of knots stored head first. Every knot behaves as the tail of the
+/// rope made of the knots before it, so ropes of different lengths can be
+/// simulated at once by tracking the positions of several knots.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RopeSimulation {
-    pub n_knots: usize,
-    pub head: KnotLink,
-    pub tail: KnotLink,
-    pub positions_tail_visited: HashMap<String, usize>,
+    pub knots: Vec<KnotPosition>,
+    pub rule: FollowRule,
+    tracked: Vec<usize>,
+    visits: Vec<Visits>,
 }
 
 impl RopeSimulation {
+    /// A rope of `n_knots` recording the positions visited by its tail.
     pub fn new_with_knots(n_knots: usize) -> Self {
-        let head: KnotLink = Knot::new(0);
-        let mut tail: Option<KnotLink> = None;
-
-        let mut temp_current_knot: KnotLink = head.clone();
-        for n in 1..n_knots {
-            let next_knot_link: KnotLink = Knot::new(n);
-
-            temp_current_knot.borrow_mut().next = Some(next_knot_link.clone());
+        Self::tracking(n_knots, &[n_knots - 1])
+    }
 
-            if n == n_knots - 1 {
-                tail = Some(next_knot_link);
-            } else {
-                temp_current_knot = next_knot_link;
-            };
+    /// A rope of `n_knots` recording the positions visited by every knot in
+    /// `tracked` (0 being the head).
+    pub fn tracking(n_knots: usize, tracked: &[usize]) -> Self {
+        assert!(n_knots > 0, "A rope needs at least one knot");
+        assert!(
+            tracked.iter().all(|&knot| knot < n_knots),
+            "Can't track a knot past the tail"
+        );
+
+        let start = KnotPosition::new();
+        Self {
+            knots: vec![start; n_knots],
+            rule: FollowRule::default(),
+            tracked: tracked.to_vec(),
+            visits: vec![HashMap::from([(start.key(), 1)]); tracked.len()],
         }
+    }
 
-        let mut simulation = Self {
-            n_knots,
-            head,
-            tail: tail.expect("Expected a tail"),
-            positions_tail_visited: HashMap::new(),
-        };
-
-        simulation
-            .positions_tail_visited
-            .insert(KnotPosition::new().to_string(), 1);
+    /// A rope of `n_knots` recording the positions visited by every knot,
+    /// which also gives the tails of every shorter rope.
+    pub fn tracking_all(n_knots: usize) -> Self {
+        Self::tracking(n_knots, &(0..n_knots).collect::<Vec<_>>())
+    }
 
-        simulation
+    pub fn with_rule(mut self, rule: FollowRule) -> Self {
+        self.rule = rule;
+        self
     }
 
-    pub fn mark_tail_position(&mut self) {
-        let key = self.tail.borrow().position.to_string();
-        if let Some(count) = self.positions_tail_visited.get_mut(&key) {
-            *count += 1;
-        } else {
-            self.positions_tail_visited.insert(key, 1);
+    fn mark_tracked_positions(&mut self) {
+        for (&knot, visits) in self.tracked.iter().zip(self.visits.iter_mut()) {
+            *visits.entry(self.knots[knot].key()).or_insert(0) += 1;
         }
     }
 
-    pub fn move_rope(&mut self, movement: Movement) {
-        // Go one step at a time, avoid moving subsequent knots if not needed.
-        let movement_unit: i32 = movement.amount / movement.amount.abs();
-
-        for _ in 0..movement.amount.abs() {
-            {
-                let mut head = self.head.borrow_mut();
+    pub fn move_rope(&mut self, movement: &Movement) {
+        self.move_rope_with(movement, |_| {});
+    }
 
-                match movement.direction {
-                    Direction::Horizontal => head.position.x_pos += movement_unit,
-                    Direction::Vertical => head.position.y_pos += movement_unit,
+    /// Same as `move_rope`, calling `on_step` with the knots after every
+    /// single step of the movement.
+    pub fn move_rope_with(
+        &mut self,
+        movement: &Movement,
+        mut on_step: impl FnMut(&[KnotPosition]),
+    ) {
+        for _ in 0..movement.steps {
+            self.knots[0].step(&movement.direction);
+
+            // Knots catching up in several steps record and show every
+            // position they pass through
+            loop {
+                self.pull_knots();
+                self.mark_tracked_positions();
+                on_step(&self.knots);
+
+                if self.is_settled() {
+                    break;
                 }
             }
-            self.affect_next_knot(&self.head);
-            self.mark_tail_position();
         }
     }
 
-    fn affect_next_knot(&self, current_link: &KnotLink) {
-        let current = current_link.borrow();
-
-        let should_affect_tail: bool = if let Some(next_link) = &current.next {
-            let mut next = next_link.borrow_mut();
-            let (dx, dy) = current.position.distance_to(&next.position);
-            if dx.abs() > 1 || dy.abs() > 1 {
-                next.position.x_pos += if dx == 0 { 0 } else { dx / dx.abs() };
-                next.position.y_pos += if dy == 0 { 0 } else { dy / dy.abs() };
-                true
-            } else {
-                false
-            }
-        } else {
-            false
-        };
+    /// Moves every knot a single step towards the one before it.
+    fn pull_knots(&mut self) {
+        for n in 1..self.knots.len() {
+            let leader = self.knots[n - 1];
+            self.rule.follow(&mut self.knots[n], &leader);
+        }
+    }
 
-        if let Some(next_link) = &current.next {
-            if should_affect_tail {
-                self.affect_next_knot(&next_link);
-            }
-        };
+    fn is_settled(&self) -> bool {
+        self.knots
+            .windows(2)
+            .all(|pair| self.rule.touches(&pair[1], &pair[0]))
+    }
+
+    /// Visit report of a tracked knot.
+    pub fn stats(&self, knot: usize) -> Option<VisitStats<'_>> {
+        self.tracked
+            .iter()
+            .position(|&tracked| tracked == knot)
+            .map(|ix| VisitStats::new(knot, &self.visits[ix]))
+    }
+
+    /// Amount of distinct positions visited by a tracked knot.
+    pub fn positions_visited_by(&self, knot: usize) -> Option<usize> {
+        self.stats(knot).map(|stats| stats.distinct())
+    }
+
+    pub fn positions_tail_visited(&self) -> usize {
+        self.positions_visited_by(self.knots.len() - 1)
+            .expect("The tail isn't tracked")
+    }
+
+    /// Distinct positions visited by the tail of every rope of 2 knots or
+    /// more whose tail is tracked, as `(rope length, positions)`.
+    pub fn distinct_by_rope_length(&self) -> Vec<(usize, usize)> {
+        (1..self.knots.len())
+            .filter_map(|knot| self.positions_visited_by(knot).map(|n| (knot + 1, n)))
+            .collect()
     }
 }
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+
+use crate::solution::knots::knot_position::KnotPosition;
+
+/// Amount of steps a knot ended on each position, the start included.
+pub type Visits = HashMap<(i32, i32, i32), usize>;
+
+/// Report over the positions visited by one knot.
+#[derive(Debug, Clone, Copy)]
+pub struct VisitStats<'a> {
+    pub knot: usize,
+    visits: &'a Visits,
+}
+
+impl<'a> VisitStats<'a> {
+    pub fn new(knot: usize, visits: &'a Visits) -> Self {
+        Self { knot, visits }
+    }
+
+    pub fn frequencies(&self) -> &'a Visits {
+        self.visits
+    }
+
+    pub fn frequency(&self, position: (i32, i32, i32)) -> usize {
+        self.visits.get(&position).copied().unwrap_or(0)
+    }
+
+    pub fn distinct(&self) -> usize {
+        self.visits.len()
+    }
+
+    /// Amount of steps recorded, the start included.
+    pub fn total(&self) -> usize {
+        self.visits.values().sum()
+    }
+
+    /// Lowest and highest corner of the box holding every visited position.
+    pub fn bounding_box(&self) -> (KnotPosition, KnotPosition) {
+        let mut low = KnotPosition::new();
+        let mut high = KnotPosition::new();
+
+        for &(x, y, z) in self.visits.keys() {
+            low = KnotPosition {
+                x_pos: low.x_pos.min(x),
+                y_pos: low.y_pos.min(y),
+                z_pos: low.z_pos.min(z),
+            };
+            high = KnotPosition {
+                x_pos: high.x_pos.max(x),
+                y_pos: high.y_pos.max(y),
+                z_pos: high.z_pos.max(z),
+            };
+        }
+
+        (low, high)
+    }
+
+    /// The `n` most visited positions, most visited first. Ties are ordered
+    /// by position so the report is stable.
+    pub fn most_visited(&self, n: usize) -> Vec<((i32, i32, i32), usize)> {
+        let mut visits = self
+            .visits
+            .iter()
+            .map(|(&position, &count)| (position, count))
+            .collect::<Vec<_>>();
+        visits.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        visits.truncate(n);
+        visits
+    }
+}
@@ -3,4 +3,7 @@ use std::io;
 
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub words_file_buffer: Option<io::BufReader<File>>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
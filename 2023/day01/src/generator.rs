@@ -0,0 +1,37 @@
+use input_gen::Rng;
+
+const WORDS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// `size` lines mixing letters, digits and spelled-out numbers. Every line
+/// has a digit, as part 1 expects.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let mut line = String::new();
+        for _ in 0..rng.index(1..=8) {
+            match rng.below(3) {
+                0 => line.push((b'1' + rng.below(9) as u8) as char),
+                1 => {
+                    let word = rng.pick(&WORDS);
+                    line.push_str(word);
+                }
+                _ => {
+                    line.extend((0..rng.index(1..=4)).map(|_| (b'a' + rng.below(26) as u8) as char))
+                }
+            }
+        }
+        if !line.bytes().any(|c| c.is_ascii_digit()) {
+            let ix = rng.index(0..=line.len());
+            line.insert(ix, (b'1' + rng.below(9) as u8) as char);
+        }
+
+        input.push_str(&line);
+        input.push('\n');
+    }
+
+    input
+}
@@ -1,4 +1,5 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::fs::File;
 use std::io::{stdin, BufReader, Read};
@@ -10,6 +11,11 @@ fn main() {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config: configuration::Configuration = configure(args);
 
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return;
+    }
+
     println!("Press enter key to start");
     stdin().read_exact(&mut [0u8]).unwrap();
 
@@ -24,22 +30,43 @@ fn main() {
         Err(e) => println!("Error: {}", e),
     }
 
-    println!("\nTotal: {}", format!("{:?}", elapsed_time));
+    println!("\nTotal: {:?}", elapsed_time);
 }
 
+/// Usage: `day01 <input> [--words <path>]`
+///
+/// The words file replaces the spelled-out numbers used in part 2, one
+/// `<word> <digit>` pair per line. `--generate <seed> <size>` prints `size`
+/// random lines instead of solving.
 pub fn configure(args: Vec<String>) -> configuration::Configuration {
-    if args.len() == 2 {
-        let path = &args[1];
-        let input_file: Option<File> = match File::open(&path) {
-            Ok(f) => Some(f),
-            Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason),
-        };
-        Configuration {
-            input_file_buffer: Some(BufReader::new(input_file.unwrap())),
-        }
-    } else {
-        Configuration {
-            input_file_buffer: None,
+    let mut input_file_buffer = None;
+    let mut words_file_buffer = None;
+    let mut generate = None;
+
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--words" {
+            let path = args.next().expect("Missing value for --words");
+            words_file_buffer = Some(BufReader::new(open(&path)));
+        } else if arg == "--generate" {
+            generate = Some(
+                input_gen::parse_args(&mut args).expect("Missing seed and size for --generate"),
+            );
+        } else {
+            input_file_buffer = Some(BufReader::new(open(&arg)));
         }
     }
+
+    Configuration {
+        input_file_buffer,
+        words_file_buffer,
+        generate,
+    }
+}
+
+fn open(path: &str) -> File {
+    match File::open(path) {
+        Ok(f) => f,
+        Err(reason) => panic!("Couldn't open file {:?}: {}", path, reason),
+    }
 }
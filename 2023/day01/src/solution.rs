@@ -1,76 +1,218 @@
 use crate::configuration::Configuration;
 use std::io::BufRead;
 
-static NUMBERS: [&str; 9] = [
-    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+use self::matcher::{FirstLast, Matcher};
+
+mod matcher;
+
+static DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+static NUMBERS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
 ];
 
-enum SearchPos {
-    Beg,
-    End,
+/// Value of a vocabulary entry, and whether it is a plain digit.
+#[derive(Clone, Copy)]
+struct Token {
+    digit: u32,
+    spelled: bool,
 }
 
 pub fn run(config: Configuration) -> Result<String, String> {
-    if config.input_file_buffer.is_some() {
-        let mut sum = 0u32;
-
-        for line in config.input_file_buffer.unwrap().lines() {
-            if let Ok(s) = line {
-                if let Some(first_number) = first_number_occurrence(&s, SearchPos::Beg) {
-                    sum += first_number * 10;
-                }
-                if let Some(last_number) = first_number_occurrence(&s, SearchPos::End) {
-                    sum += last_number;
-                }
-            }
+    if let Some(input_buf) = config.input_file_buffer {
+        let words = match config.words_file_buffer {
+            Some(words_buf) => parse_words(words_buf)?,
+            None => NUMBERS
+                .iter()
+                .map(|&(word, digit)| (word.to_string(), digit))
+                .collect(),
+        };
+        let matcher = build_matcher(&words);
+
+        let mut part_1 = 0u32;
+        let mut part_2 = 0u32;
+
+        for line in input_buf.lines() {
+            let line = line.map_err(|e| e.to_string())?;
+            let (digits_only, with_words) = calibration_values(&matcher, &line);
+            part_1 += digits_only;
+            part_2 += with_words;
         }
-        Ok(sum.to_string())
+
+        Ok(format!("Part 1: {}, Part 2: {}", part_1, part_2))
     } else {
         Err(String::from("Need input file!"))
     }
 }
 
-fn first_number_occurrence(buf: &String, starting_pos: SearchPos) -> Option<u32> {
-    let last_ix = buf.len() - 1;
-    let mut start_ix = if let SearchPos::Beg = starting_pos {
-        0
-    } else {
-        last_ix
-    };
+fn parse_words(words_buf: impl BufRead) -> Result<Vec<(String, u32)>, String> {
+    let mut words = vec![];
 
-    loop {
-        if let Ok(digit) = buf[start_ix..=start_ix].parse::<u32>() {
-            return Some(digit);
+    for line in words_buf.lines() {
+        let line = line.map_err(|e| e.to_string())?;
+        if line.trim().is_empty() {
+            continue;
         }
 
-        let end_ix = if last_ix - start_ix > 5 {
-            start_ix + 4
-        } else {
-            last_ix
-        };
+        let parsed = line
+            .split_once(' ')
+            .and_then(|(word, digit)| Some((word.trim(), digit.trim().parse::<u32>().ok()?)));
+        match parsed {
+            Some((word, digit)) if !word.is_empty() && digit <= 9 => {
+                words.push((word.to_string(), digit))
+            }
+            _ => return Err(format!("Expected \"<word> <digit>\", got {:?}", line)),
+        }
+    }
+
+    Ok(words)
+}
 
-        let slice = &buf[start_ix..=end_ix];
+fn build_matcher(words: &[(String, u32)]) -> Matcher<Token> {
+    let digits = DIGITS.iter().map(|&(pattern, digit)| {
+        (
+            pattern,
+            Token {
+                digit,
+                spelled: false,
+            },
+        )
+    });
+    let words = words.iter().map(|(pattern, digit)| {
+        (
+            pattern.as_str(),
+            Token {
+                digit: *digit,
+                spelled: true,
+            },
+        )
+    });
 
-        for (i, number_slice) in NUMBERS.into_iter().enumerate() {
-            if slice.starts_with(number_slice) {
-                return Some(i as u32 + 1);
-            }
+    Matcher::new(digits.chain(words))
+}
+
+/// Calibration values of a line, considering digits only (part 1) and
+/// digits or spelled numbers (part 2). Lines without a match count as 0.
+fn calibration_values(matcher: &Matcher<Token>, line: &str) -> (u32, u32) {
+    let mut digits_only = FirstLast::new();
+    let mut with_words = FirstLast::new();
+
+    for m in matcher.find_iter(line) {
+        if !m.value.spelled {
+            digits_only.push(m);
         }
+        with_words.push(m);
+    }
 
-        match starting_pos {
-            SearchPos::Beg => {
-                if start_ix == last_ix {
-                    return None;
-                } else {
-                    start_ix += 1;
-                }
+    (value_of(&digits_only), value_of(&with_words))
+}
+
+fn value_of(first_last: &FirstLast<Token>) -> u32 {
+    match (first_last.first, first_last.last) {
+        (Some(first), Some(last)) => first.value.digit * 10 + last.value.digit,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::{build_matcher, calibration_values, DIGITS, NUMBERS};
+
+    fn english() -> Vec<(String, u32)> {
+        NUMBERS
+            .iter()
+            .map(|&(word, digit)| (word.to_string(), digit))
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let matcher = build_matcher(&english());
+        let total = include_str!("../test")
+            .lines()
+            .map(|line| calibration_values(&matcher, line).1)
+            .sum::<u32>();
+
+        assert_eq!(total, 281);
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let matcher = build_matcher(&english());
+
+        assert_eq!(calibration_values(&matcher, "eightwo"), (0, 82));
+        assert_eq!(calibration_values(&matcher, "1oneight"), (11, 18));
+    }
+
+    #[test]
+    fn empty_line() {
+        let matcher = build_matcher(&english());
+
+        assert_eq!(calibration_values(&matcher, ""), (0, 0));
+    }
+
+    #[test]
+    fn other_vocabulary() {
+        let german = vec![("eins".to_string(), 1), ("zwei".to_string(), 2)];
+        let matcher = build_matcher(&german);
+
+        assert_eq!(calibration_values(&matcher, "xzweinsx3"), (33, 23));
+    }
+
+    /// Tries every vocabulary entry at every position.
+    fn naive_calibration_values(line: &str) -> (u32, u32) {
+        let at = |ix: usize, vocabulary: &[(&str, u32)]| {
+            vocabulary
+                .iter()
+                .find(|(pattern, _)| line[ix..].starts_with(pattern))
+                .map(|&(_, digit)| digit)
+        };
+        let value = |with_words: bool| {
+            let digits = (0..line.len())
+                .filter_map(|ix| {
+                    at(ix, &DIGITS).or_else(|| at(ix, &NUMBERS).filter(|_| with_words))
+                })
+                .collect::<Vec<_>>();
+            match (digits.first(), digits.last()) {
+                (Some(first), Some(last)) => first * 10 + last,
+                _ => 0,
             }
-            SearchPos::End => {
-                if start_ix == 0 {
-                    return None;
-                } else {
-                    start_ix -= 1;
-                }
+        };
+
+        (value(false), value(true))
+    }
+
+    #[test]
+    fn generated_lines() {
+        let matcher = build_matcher(&english());
+
+        for seed in 0..20 {
+            for line in generate(seed, 500).lines() {
+                assert_eq!(
+                    calibration_values(&matcher, line),
+                    naive_calibration_values(line),
+                    "{}",
+                    line
+                );
             }
         }
     }
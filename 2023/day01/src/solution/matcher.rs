@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, VecDeque};
+
+/// Multi-pattern matcher (Aho-Corasick automaton). Reports every occurrence
+/// of every pattern in a single pass, including overlapping ones like the
+/// `eight` and `two` in `eightwo`.
+pub struct Matcher<T> {
+    nodes: Vec<Node>,
+    patterns: Vec<(usize, T)>,
+}
+
+#[derive(Default)]
+struct Node {
+    goto: BTreeMap<u8, usize>,
+    fail: usize,
+    outputs: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<T> {
+    pub start: usize,
+    pub end: usize,
+    pub value: T,
+}
+
+impl<T: Copy> Matcher<T> {
+    pub fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, T)>) -> Self {
+        let mut matcher = Matcher {
+            nodes: vec![Node::default()],
+            patterns: vec![],
+        };
+
+        for (pattern, value) in patterns {
+            if !pattern.is_empty() {
+                matcher.insert(pattern, value);
+            }
+        }
+        matcher.build_fail_links();
+
+        matcher
+    }
+
+    fn insert(&mut self, pattern: &str, value: T) {
+        let mut state = 0;
+        for byte in pattern.bytes() {
+            state = match self.nodes[state].goto.get(&byte) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[state].goto.insert(byte, next);
+                    next
+                }
+            };
+        }
+
+        self.patterns.push((pattern.len(), value));
+        self.nodes[state].outputs.push(self.patterns.len() - 1);
+    }
+
+    fn build_fail_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[0].goto.values().copied().collect();
+
+        while let Some(state) = queue.pop_front() {
+            let edges: Vec<(u8, usize)> = self.nodes[state]
+                .goto
+                .iter()
+                .map(|(&byte, &next)| (byte, next))
+                .collect();
+
+            for (byte, next) in edges {
+                let mut fail = self.nodes[state].fail;
+                while fail != 0 && !self.nodes[fail].goto.contains_key(&byte) {
+                    fail = self.nodes[fail].fail;
+                }
+                let fail = match self.nodes[fail].goto.get(&byte) {
+                    Some(&target) if target != next => target,
+                    _ => 0,
+                };
+
+                self.nodes[next].fail = fail;
+                let inherited = self.nodes[fail].outputs.clone();
+                self.nodes[next].outputs.extend(inherited);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    fn step(&self, mut state: usize, byte: u8) -> usize {
+        loop {
+            if let Some(&next) = self.nodes[state].goto.get(&byte) {
+                return next;
+            }
+            if state == 0 {
+                return 0;
+            }
+            state = self.nodes[state].fail;
+        }
+    }
+
+    /// All matches, ordered by end position. Positions are byte offsets,
+    /// `end` is exclusive.
+    pub fn find_iter<'h>(&'h self, haystack: &'h str) -> impl Iterator<Item = Match<T>> + 'h {
+        haystack
+            .bytes()
+            .enumerate()
+            .scan(0usize, move |state, (ix, byte)| {
+                *state = self.step(*state, byte);
+                Some((ix + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.nodes[state].outputs.iter().map(move |&pattern| {
+                    let (len, value) = self.patterns[pattern];
+                    Match {
+                        start: end - len,
+                        end,
+                        value,
+                    }
+                })
+            })
+    }
+}
+
+/// Keeps track of the first (leftmost start) and last (rightmost start)
+/// match seen. Ties go to the longest match.
+pub struct FirstLast<T> {
+    pub first: Option<Match<T>>,
+    pub last: Option<Match<T>>,
+}
+
+impl<T: Copy> FirstLast<T> {
+    pub fn new() -> Self {
+        FirstLast {
+            first: None,
+            last: None,
+        }
+    }
+
+    pub fn push(&mut self, m: Match<T>) {
+        let replaces_first = self
+            .first
+            .is_none_or(|first| (m.start, first.end) < (first.start, m.end));
+        if replaces_first {
+            self.first = Some(m);
+        }
+
+        let replaces_last = self
+            .last
+            .is_none_or(|last| (m.start, m.end) > (last.start, last.end));
+        if replaces_last {
+            self.last = Some(m);
+        }
+    }
+}
@@ -1,23 +1,62 @@
-use std::fs::File;
-use std::io;
+use std::fs::{self, File};
+use std::io::{self, ErrorKind};
 
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    pub bag_spec: Option<String>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
 
 impl Configuration {
+    /// Usage: `day02 <input> [--bag "12 red, 13 green, 14 blue" | --bag-file <path>]`
+    ///
+    /// A bag file uses the same `<amount> <colour>` items as the CLI option,
+    /// separated by commas or newlines. `--generate <seed> <size>` prints
+    /// `size` random games instead of solving.
     pub fn new(args: Vec<String>) -> io::Result<Self> {
-        if args.len() == 2 {
-            let path = &args[1];
-            let input_file = File::open(&path)?;
+        let mut input_file_buffer = None;
+        let mut bag_spec = None;
+        let mut generate = None;
 
-            Ok(Configuration {
-                input_file_buffer: Some(io::BufReader::new(input_file)),
-            })
-        } else {
-            Ok(Configuration {
-                input_file_buffer: None,
-            })
+        let mut args = args.into_iter().skip(1);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--bag" => {
+                    bag_spec = Some(args.next().ok_or_else(|| missing_value(&arg))?);
+                }
+                "--bag-file" => {
+                    let path = args.next().ok_or_else(|| missing_value(&arg))?;
+                    let contents = fs::read_to_string(path)?;
+                    let items: Vec<&str> = contents
+                        .lines()
+                        .map(str::trim)
+                        .filter(|line| !line.is_empty())
+                        .collect();
+                    bag_spec = Some(items.join(","));
+                }
+                "--generate" => {
+                    generate =
+                        Some(input_gen::parse_args(&mut args).ok_or_else(|| missing_value(&arg))?);
+                }
+                path => {
+                    let input_file = File::open(path)?;
+                    input_file_buffer = Some(io::BufReader::new(input_file));
+                }
+            }
         }
+
+        Ok(Configuration {
+            input_file_buffer,
+            bag_spec,
+            generate,
+        })
     }
 }
+
+fn missing_value(option: &str) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidInput,
+        format!("Missing value for {}", option),
+    )
+}
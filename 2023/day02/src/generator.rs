@@ -0,0 +1,30 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+const COLOURS: [&str; 3] = ["red", "green", "blue"];
+
+/// `size` games of 1 to 6 rounds, each drawing 1 to 15 cubes of up to
+/// three colours, so some games don't fit in the puzzle's bag.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for id in 1..=size {
+        let rounds = (0..rng.index(1..=6))
+            .map(|_| {
+                let mut colours = COLOURS;
+                rng.shuffle(&mut colours);
+                colours[..rng.index(1..=3)]
+                    .iter()
+                    .map(|colour| format!("{} {}", rng.between(1..=15), colour))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+
+        writeln!(input, "Game {}: {}", id, rounds.join("; ")).unwrap();
+    }
+
+    input
+}
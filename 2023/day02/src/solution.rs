@@ -1,43 +1,115 @@
 use crate::configuration::Configuration;
 use std::{
-    io::{self, BufRead, ErrorKind},
+    io::{self, BufRead},
     str::FromStr,
 };
 
-use self::game::Game;
+use self::{cube_set::CubeSet, game::Game};
 
+mod cube_set;
 mod game;
-mod round;
 
-const RED_CUBES_AMOUNT: usize = 12;
-const GREEN_CUBES_AMOUNT: usize = 13;
-const BLUE_CUBES_AMOUNT: usize = 14;
+const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
 
 pub fn run(config: Configuration) -> io::Result<String> {
     if let Some(input_buf) = config.input_file_buffer {
-        let mut sum = 0usize;
-        let mut powers = 0usize;
+        let bag = CubeSet::from_str(config.bag_spec.as_deref().unwrap_or(DEFAULT_BAG))?;
 
-        for (index, line) in input_buf.lines().enumerate() {
-            if let Ok(s) = line {
-                let game = Game::from_str(get_game_str(&s))?;
-
-                if game.is_game_possible() {
-                    // use index instead of parsing game number
-                    sum += index + 1
-                }
-
-                powers += game.find_min_power();
+        let mut games = vec![];
+        for line in input_buf.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                games.push(Game::from_str(&line)?);
             }
         }
+
+        let (sum, powers) = analyse(&games, &bag);
+
         Ok(format!("Part 1: {}, Part 2: {}", sum, powers))
     } else {
-        Err(io::Error::new(ErrorKind::Other, "Input file required"))
+        Err(io::Error::other("Input file required"))
     }
 }
 
-fn get_game_str(line: &'_ str) -> &'_ str {
-    let game_split_index = line.find(":").expect("Expected colon");
+/// Sum of the ids of the games possible with `bag`, and the sum of the powers
+/// of every game over the bag's colours.
+fn analyse(games: &[Game], bag: &CubeSet) -> (usize, usize) {
+    let sum = games
+        .iter()
+        .filter(|game| game.is_possible(bag))
+        .map(|game| game.id)
+        .sum();
+    let powers = games.iter().map(|game| game.power(bag.colours())).sum();
+
+    (sum, powers)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::generator::generate;
+    use crate::solution::{analyse, cube_set::CubeSet, game::Game, DEFAULT_BAG};
+
+    const EXAMPLE: &str = include_str!("../test");
+
+    fn parse_games(input: &str) -> Vec<Game> {
+        input
+            .lines()
+            .map(|line| Game::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn example_default_bag() {
+        let bag = CubeSet::from_str(DEFAULT_BAG).unwrap();
+
+        assert_eq!(analyse(&parse_games(EXAMPLE), &bag), (8, 2286));
+    }
+
+    #[test]
+    fn game_id_from_header() {
+        let game = Game::from_str("Game 42: 1 red; 2 purple, 3 red").unwrap();
+
+        assert_eq!(game.id, 42);
+        assert_eq!(game.minimum_bag().get("red"), 3);
+        assert_eq!(game.minimum_bag().get("purple"), 2);
+        assert_eq!(game.power(["red", "purple"]), 6);
+    }
+
+    #[test]
+    fn unknown_colour_is_impossible() {
+        let bag = CubeSet::from_str("5 red").unwrap();
+
+        assert!(Game::from_str("Game 1: 2 red").unwrap().is_possible(&bag));
+        assert!(!Game::from_str("Game 1: 2 red, 1 teal")
+            .unwrap()
+            .is_possible(&bag));
+    }
 
-    &line[game_split_index + 2..]
+    #[test]
+    fn generated_games() {
+        let bag = CubeSet::from_str(DEFAULT_BAG).unwrap();
+
+        for seed in 0..20 {
+            let input = generate(seed, 300);
+            let (mut possible, mut power) = (0, 0);
+            for (ix, line) in input.lines().enumerate() {
+                let (_, rounds) = line.split_once(": ").unwrap();
+                let mut maximum = [0; 3];
+                for draw in rounds.split("; ").flat_map(|round| round.split(", ")) {
+                    let (amount, colour) = draw.split_once(' ').unwrap();
+                    let colour = ["red", "green", "blue"].iter().position(|&c| c == colour);
+                    let amount = amount.parse::<usize>().unwrap();
+                    maximum[colour.unwrap()] = maximum[colour.unwrap()].max(amount);
+                }
+                if maximum[0] <= 12 && maximum[1] <= 13 && maximum[2] <= 14 {
+                    possible += ix + 1;
+                }
+                power += maximum.iter().product::<usize>();
+            }
+
+            assert_eq!(analyse(&parse_games(&input), &bag), (possible, power));
+        }
+    }
 }
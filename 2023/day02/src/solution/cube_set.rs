@@ -0,0 +1,78 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, ErrorKind},
+    str::FromStr,
+};
+
+/// Amount of cubes per colour, used both for a revealed round and for a bag.
+/// Colours are free-form, so any colour set is supported.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CubeSet {
+    amounts: BTreeMap<String, usize>,
+}
+
+impl CubeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, colour: &str) -> usize {
+        self.amounts.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn set(&mut self, colour: &str, amount: usize) {
+        self.amounts.insert(colour.to_string(), amount);
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str> {
+        self.amounts.keys().map(String::as_str)
+    }
+
+    /// Whether every amount in this set fits in `bag`. Colours missing from
+    /// the bag have a limit of zero.
+    pub fn fits_in(&self, bag: &CubeSet) -> bool {
+        self.amounts
+            .iter()
+            .all(|(colour, &amount)| amount <= bag.get(colour))
+    }
+
+    /// Raises every amount to at least the one in `other`.
+    pub fn extend_max(&mut self, other: &CubeSet) {
+        for (colour, &amount) in other.amounts.iter() {
+            let current = self.amounts.entry(colour.clone()).or_insert(0);
+            if amount > *current {
+                *current = amount;
+            }
+        }
+    }
+
+    /// Product of the amounts of `colours`.
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = &'a str>) -> usize {
+        colours.into_iter().map(|colour| self.get(colour)).product()
+    }
+}
+
+impl FromStr for CubeSet {
+    type Err = io::Error;
+
+    /// Parses comma separated `<amount> <colour>` items, e.g. `3 blue, 4 red`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cube_set = CubeSet::new();
+
+        for item in s.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (amount, colour) = item.split_once(' ').ok_or_else(|| invalid_item(item))?;
+            let amount = amount.parse::<usize>().map_err(|_| invalid_item(item))?;
+
+            cube_set.set(colour.trim(), amount);
+        }
+
+        Ok(cube_set)
+    }
+}
+
+fn invalid_item(item: &str) -> io::Error {
+    io::Error::new(
+        ErrorKind::InvalidData,
+        format!("Expected \"<amount> <colour>\", got \"{}\"", item),
+    )
+}
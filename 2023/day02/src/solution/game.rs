@@ -1,63 +1,60 @@
-use std::{io, str::FromStr};
+use std::{
+    io::{self, ErrorKind},
+    str::FromStr,
+};
 
-use super::round::Round;
+use super::cube_set::CubeSet;
 
 pub struct Game {
-    rounds: Vec<Round>,
+    pub id: usize,
+    rounds: Vec<CubeSet>,
 }
 
 impl Game {
-    fn new() -> Self {
-        Self { rounds: vec![] }
+    pub fn is_possible(&self, bag: &CubeSet) -> bool {
+        self.rounds.iter().all(|round| round.fits_in(bag))
     }
 
-    pub fn is_game_possible(&self) -> bool {
-        for round in self.rounds.iter() {
-            if !round.is_round_possible() {
-                return false;
-            }
-        }
+    /// Smallest bag that makes every round of the game possible.
+    pub fn minimum_bag(&self) -> CubeSet {
+        let mut bag = CubeSet::new();
+        self.rounds.iter().for_each(|round| bag.extend_max(round));
 
-        true
+        bag
     }
 
-    pub fn find_min_power(&self) -> usize {
-        let mut min_red = 0usize;
-        let mut min_green = 0usize;
-        let mut min_blue = 0usize;
-
-        self.rounds.iter().for_each(|round| {
-            if let Some(amount) = round.red {
-                if amount > min_red {
-                    min_red = amount;
-                }
-            }
-            if let Some(amount) = round.green {
-                if amount > min_green {
-                    min_green = amount;
-                }
-            }
-            if let Some(amount) = round.blue {
-                if amount > min_blue {
-                    min_blue = amount;
-                }
-            }
-        });
-
-        min_red * min_green * min_blue
+    /// Power of the minimum bag over `colours`. A colour that never shows up
+    /// contributes zero.
+    pub fn power<'a>(&self, colours: impl IntoIterator<Item = &'a str>) -> usize {
+        self.minimum_bag().power(colours)
     }
 }
 
 impl FromStr for Game {
     type Err = io::Error;
 
-    fn from_str(game_str: &str) -> Result<Self, Self::Err> {
-        let mut game = Game::new();
-
-        game_str
-            .split(";")
-            .for_each(|round_str| game.rounds.push(Round::from_str(round_str).unwrap()));
-
-        Ok(game)
+    /// Parses a `Game <id>: <round>; <round>; ...` line.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let (header, rounds_str) = line
+            .split_once(':')
+            .ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "Expected colon"))?;
+
+        let id = header
+            .trim()
+            .strip_prefix("Game ")
+            .and_then(|id| id.trim().parse::<usize>().ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Expected \"Game <id>\", got \"{}\"", header),
+                )
+            })?;
+
+        let rounds = rounds_str
+            .split(';')
+            .map(CubeSet::from_str)
+            .collect::<io::Result<Vec<CubeSet>>>()?;
+
+        Ok(Game { id, rounds })
     }
 }
@@ -0,0 +1,36 @@
+use input_gen::Rng;
+
+const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+
+/// A `size` x `size` schematic of numbers up to three digits, symbols and
+/// dots. Gears show up often, as `*` is drawn as often as every other
+/// symbol together.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::with_capacity(size * (size + 1));
+
+    for _ in 0..size {
+        let mut row = Vec::with_capacity(size);
+        while row.len() < size {
+            let after_number = row.last().is_some_and(u8::is_ascii_digit);
+            if !after_number && rng.chance(0.2) {
+                let digits = rng.index(1..=3).min(size - row.len());
+                row.push(b'1' + rng.below(9) as u8);
+                row.extend((1..digits).map(|_| b'0' + rng.below(10) as u8));
+            } else if rng.chance(0.1) {
+                row.push(if rng.chance(0.5) {
+                    b'*'
+                } else {
+                    *rng.pick(SYMBOLS)
+                });
+            } else {
+                row.push(b'.');
+            }
+        }
+
+        input.push_str(std::str::from_utf8(&row).unwrap());
+        input.push('\n');
+    }
+
+    input
+}
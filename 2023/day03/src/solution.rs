@@ -19,3 +19,107 @@ pub fn run(config: Configuration) -> io::Result<String> {
         Err(io::Error::new(ErrorKind::Other, "Input file required"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::generator::generate;
+    use crate::solution::schematic::Schematic;
+
+    /// Scans the numbers of every row and looks around each of them.
+    fn naive_sums(input: &str) -> (usize, usize) {
+        let grid = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+        let mut part_sum = 0;
+        let mut gears: Vec<((usize, usize), usize)> = vec![];
+
+        for (y, row) in grid.iter().enumerate() {
+            let mut x = 0;
+            while x < row.len() {
+                if !row[x].is_ascii_digit() {
+                    x += 1;
+                    continue;
+                }
+                let start = x;
+                while x < row.len() && row[x].is_ascii_digit() {
+                    x += 1;
+                }
+                let number = std::str::from_utf8(&row[start..x]).unwrap();
+                let number = number.parse::<usize>().unwrap();
+
+                let mut is_part = false;
+                let around = grid.iter().enumerate().skip(y.saturating_sub(1)).take(3);
+                for (ny, neighbour_row) in around.filter(|&(ny, _)| ny <= y + 1) {
+                    let from = start.saturating_sub(1);
+                    for (nx, &c) in neighbour_row.iter().enumerate().take(x + 1).skip(from) {
+                        if c != b'.' && !c.is_ascii_digit() {
+                            is_part = true;
+                        }
+                        if c == b'*' {
+                            gears.push(((nx, ny), number));
+                        }
+                    }
+                }
+                if is_part {
+                    part_sum += number;
+                }
+            }
+        }
+
+        gears.sort();
+        let gear_sum = gears
+            .chunk_by(|a, b| a.0 == b.0)
+            .filter(|numbers| numbers.len() == 2)
+            .map(|numbers| numbers[0].1 * numbers[1].1)
+            .sum();
+
+        (part_sum, gear_sum)
+    }
+
+    #[test]
+    fn example() {
+        let schematic = Schematic::new(include_str!("../test").as_bytes()).unwrap();
+
+        assert_eq!(schematic.find_component_sum(), 4361);
+        assert_eq!(schematic.find_gear_ratio_sum(), 467835);
+    }
+
+    #[test]
+    fn numbers_end_with_their_row() {
+        let schematic = Schematic::new("...12\n3*...\n".as_bytes()).unwrap();
+
+        assert_eq!(schematic.find_component_sum(), 3);
+    }
+
+    #[test]
+    fn numbers_next_to_two_symbols_count_once() {
+        let schematic = Schematic::new("#12$\n".as_bytes()).unwrap();
+
+        assert_eq!(schematic.find_component_sum(), 12);
+    }
+
+    #[test]
+    fn gears_have_exactly_two_numbers() {
+        let schematic = Schematic::new("1.2\n.*.\n3..\n".as_bytes()).unwrap();
+
+        assert_eq!(schematic.find_gear_ratio_sum(), 0);
+    }
+
+    #[test]
+    fn generated_schematics() {
+        for (seed, size) in (0..20).zip((5..).step_by(7)) {
+            let input = generate(seed, size);
+            let schematic = Schematic::new(input.as_bytes()).unwrap();
+
+            assert_eq!(input.lines().count(), size);
+            assert!(input.lines().all(|row| row.len() == size));
+            assert_eq!(
+                (
+                    schematic.find_component_sum(),
+                    schematic.find_gear_ratio_sum()
+                ),
+                naive_sums(&input),
+                "\n{}",
+                input
+            );
+        }
+    }
+}
@@ -2,9 +2,8 @@ use std::{
     cell::RefCell,
     char,
     collections::{HashMap, HashSet},
-    fs::File,
     hash::Hash,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead},
     rc::Rc,
     usize, vec,
 };
@@ -96,7 +95,7 @@ pub struct Schematic {
 }
 
 impl Schematic {
-    pub fn new(reader: BufReader<File>) -> io::Result<Self> {
+    pub fn new(reader: impl BufRead) -> io::Result<Self> {
         let mut matrix = SimpleMatrix::<char>::new();
         let mut components: Vec<Rc<Component>> = vec![];
         let mut symbol_adjacency_map: HashMap<Rc<Cell<char>>, HashSet<Rc<Component>>> =
@@ -112,13 +111,8 @@ impl Schematic {
                     // Create component
                     if NUMBERS.contains(&c) {
                         component_cell_buf.push(rc_cell.clone());
-                    } else if !component_cell_buf.is_empty() {
-                        let mut component = Component { cells: vec![] };
-                        component_cell_buf
-                            .iter()
-                            .for_each(|rc_cell| component.cells.push(rc_cell.clone()));
-                        components.push(Rc::new(component));
-                        component_cell_buf.clear();
+                    } else {
+                        flush_component(&mut component_cell_buf, &mut components);
                     }
 
                     matrix.add_adjacent(rc_cell.clone());
@@ -127,6 +121,8 @@ impl Schematic {
                     matrix.cells.push(rc_cell);
                 }
             }
+            // Numbers end with their row
+            flush_component(&mut component_cell_buf, &mut components);
         }
 
         // Set adjacent symbols
@@ -162,22 +158,22 @@ impl Schematic {
     }
 
     pub fn find_component_sum(&self) -> usize {
-        let mut sum = 0usize;
-
-        for (_rc_cell, adjacent_component_refs) in &self.symbol_adjacency_map {
-            for component in adjacent_component_refs {
-                sum += component.get_number();
-            }
-        }
-
-        sum
+        // A number next to several symbols is still a single part
+        let mut counted = HashSet::new();
+
+        self.symbol_adjacency_map
+            .values()
+            .flatten()
+            .filter(|component| counted.insert(Rc::as_ptr(component)))
+            .map(|component| component.get_number())
+            .sum()
     }
 
     pub fn find_gear_ratio_sum(&self) -> usize {
         let mut sum = 0usize;
 
         for (rc_cell, adjacent_component_refs) in &self.symbol_adjacency_map {
-            if rc_cell.value == '*' && adjacent_component_refs.len() > 1 {
+            if rc_cell.value == '*' && adjacent_component_refs.len() == 2 {
                 let mut ratio = 1usize;
                 for component in adjacent_component_refs {
                     ratio *= component.get_number();
@@ -188,4 +184,12 @@ impl Schematic {
 
         sum
     }
-}
\ No newline at end of file
+}
+
+fn flush_component(cell_buf: &mut Vec<Rc<Cell<char>>>, components: &mut Vec<Rc<Component>>) {
+    if !cell_buf.is_empty() {
+        components.push(Rc::new(Component {
+            cells: std::mem::take(cell_buf),
+        }));
+    }
+}
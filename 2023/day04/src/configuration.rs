@@ -3,20 +3,28 @@ use std::io;
 
 pub struct Configuration {
     pub input_file_buffer: Option<io::BufReader<File>>,
+    /// Seed and size of a random input to print instead of solving.
+    pub generate: Option<(u64, usize)>,
 }
 
 impl Configuration {
+    /// Usage: `day04 <input>`, or `day04 --generate <seed> <size>` to print a
+    /// random input instead.
     pub fn new(args: Vec<String>) -> io::Result<Self> {
+        let generate = input_gen::parse_generate_flag(&args)?;
+
         if args.len() == 2 {
             let path = &args[1];
             let input_file = File::open(&path)?;
 
             Ok(Configuration {
                 input_file_buffer: Some(io::BufReader::new(input_file)),
+                generate,
             })
         } else {
             Ok(Configuration {
                 input_file_buffer: None,
+                generate,
             })
         }
     }
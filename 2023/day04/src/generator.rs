@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+const WINNING: usize = 10;
+const SCRATCHED: usize = 25;
+/// Copies of a card won from earlier cards multiply quickly, the card
+/// total is kept below this many instances per card.
+const MAX_INSTANCES_PER_CARD: usize = 100;
+
+/// `size` cards of 10 winning and 25 scratched numbers between 1 and 99.
+/// Like in the puzzle, no card wins copies of cards past the end of the
+/// table. The generator follows the amount of instances of every card to
+/// keep the total from exploding.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let budget = MAX_INSTANCES_PER_CARD * size;
+    let mut instances = vec![1usize; size];
+    let mut total = size;
+    let mut input = String::new();
+
+    for id in 0..size {
+        let copies = instances[id];
+        let mut matches = if rng.chance(0.5) {
+            0
+        } else {
+            rng.index(1..=WINNING).min(size - id - 1)
+        };
+        while matches > 0 && total + copies * matches > budget {
+            matches -= 1;
+        }
+        for won in &mut instances[id + 1..=id + matches] {
+            *won += copies;
+        }
+        total += copies * matches;
+
+        let mut numbers = (1..=99).collect::<Vec<usize>>();
+        rng.shuffle(&mut numbers);
+        let winning = &numbers[..WINNING];
+        let mut scratched = numbers[..matches].to_vec();
+        scratched.extend(&numbers[WINNING..WINNING + SCRATCHED - matches]);
+        rng.shuffle(&mut scratched);
+
+        let format = |numbers: &[usize]| {
+            numbers
+                .iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        writeln!(
+            input,
+            "Card {:>3}: {} | {}",
+            id + 1,
+            format(winning),
+            format(&scratched)
+        )
+        .unwrap();
+    }
+
+    input
+}
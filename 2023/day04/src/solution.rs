@@ -53,3 +53,108 @@ pub fn count_recursively(cards: &Vec<Card>, l_range_limit: usize, h_range_limit:
 
     sum
 }
+
+#[cfg(test)]
+mod tests {
+    use input_gen::differential::{smaller, without_blocks, Differential};
+
+    use crate::generator::generate;
+    use crate::solution::{card::Card, count_recursively};
+
+    fn parse_cards(input: &str) -> Vec<Card> {
+        input
+            .lines()
+            .map(|line| Card::from_str(line.to_string()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn example() {
+        let cards = parse_cards(include_str!("../test"));
+
+        assert_eq!(cards.iter().map(Card::count_points).sum::<usize>(), 13);
+        assert_eq!(count_recursively(&cards, 0, cards.len() - 1), 30);
+    }
+
+    #[test]
+    fn generated_cards() {
+        for seed in 0..10 {
+            let cards = parse_cards(&generate(seed, 100));
+
+            assert_eq!(cards.len(), 100);
+            // Cards never win copies of cards past the end of the table
+            for (ix, card) in cards.iter().enumerate() {
+                assert!(ix + card.count_matches() < cards.len());
+            }
+            assert!(count_recursively(&cards, 0, cards.len() - 1) >= cards.len());
+        }
+    }
+
+    /// A card with `matches` winning numbers among the scratched ones.
+    fn card(id: usize, matches: usize) -> Card {
+        let winning = (1..=matches).chain([98]).map(|n| n.to_string());
+        let scratched = (1..=matches).chain([99]).map(|n| n.to_string());
+        let line = format!(
+            "Card {}: {} | {}",
+            id,
+            winning.collect::<Vec<_>>().join(" "),
+            scratched.collect::<Vec<_>>().join(" ")
+        );
+        Card::from_str(line).unwrap()
+    }
+
+    /// Fewer cards or fewer matches, as long as there is a card left and
+    /// none of them wins copies past the end of the table.
+    fn shrink(matches: &[usize]) -> Vec<Vec<usize>> {
+        let mut candidates = without_blocks(matches);
+        for (ix, &m) in matches.iter().enumerate() {
+            candidates.extend(smaller(m as u64).into_iter().map(|smaller| {
+                let mut candidate = matches.to_vec();
+                candidate[ix] = smaller as usize;
+                candidate
+            }));
+        }
+        candidates.retain(|candidate| {
+            !candidate.is_empty()
+                && candidate
+                    .iter()
+                    .enumerate()
+                    .all(|(ix, m)| ix + m < candidate.len())
+        });
+        candidates
+    }
+
+    #[test]
+    fn recursion_matches_instance_counts() {
+        let differential = Differential::new(
+            |seed| {
+                parse_cards(&generate(seed, 1 + seed as usize % 50))
+                    .iter()
+                    .map(Card::count_matches)
+                    .collect::<Vec<_>>()
+            },
+            |matches| {
+                let cards = matches
+                    .iter()
+                    .enumerate()
+                    .map(|(ix, &m)| card(ix + 1, m))
+                    .collect::<Vec<_>>();
+                count_recursively(&cards, 0, cards.len() - 1)
+            },
+            |matches| {
+                let mut instances = vec![1; matches.len()];
+                for (ix, &m) in matches.iter().enumerate() {
+                    for won in ix + 1..=ix + m {
+                        instances[won] += instances[ix];
+                    }
+                }
+                instances.iter().sum::<usize>()
+            },
+        )
+        .shrink(|matches| shrink(matches));
+
+        if let Err(mismatch) = differential.run(0..100) {
+            panic!("{:?}", mismatch);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+use crate::solution::MAPS;
+
+/// Every map works on `0..size * SPAN`, which keeps small almanacs small
+/// enough to check seed by seed.
+const SPAN: u64 = 100;
+
+/// An almanac whose maps have up to `size` ranges each. Every map cuts
+/// `0..size * 100` into segments and moves them around, leaving a fifth of
+/// them unmapped. Seeds are 1 to 10 ranges, a few of them reaching past
+/// the mapped numbers.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let universe = size.max(1) as u64 * SPAN;
+
+    let pairs = (size / 4).clamp(1, 10);
+    let seeds = (0..pairs)
+        .map(|_| {
+            let start = rng.below(universe);
+            let length = rng.between(1..=(universe / 10).max(1));
+            format!("{} {}", start, length)
+        })
+        .collect::<Vec<_>>();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+
+    for header in MAPS {
+        let mut cuts = (0..size.max(1))
+            .map(|_| rng.between(1..=universe - 1))
+            .collect::<Vec<_>>();
+        cuts.extend([0, universe]);
+        cuts.sort_unstable();
+        cuts.dedup();
+
+        let sources = cuts
+            .windows(2)
+            .map(|w| (w[0], w[1] - w[0]))
+            .collect::<Vec<_>>();
+        let mut order = (0..sources.len()).collect::<Vec<_>>();
+        rng.shuffle(&mut order);
+
+        let mut ranges = vec![];
+        let mut destination = 0;
+        for ix in order {
+            let (source, length) = sources[ix];
+            if !rng.chance(0.2) {
+                ranges.push(format!("{} {} {}", destination, source, length));
+            }
+            destination += length;
+        }
+        rng.shuffle(&mut ranges);
+
+        write!(input, "\n{}\n", header).unwrap();
+        for range in ranges {
+            writeln!(input, "{}", range).unwrap();
+        }
+    }
+
+    input
+}
@@ -1,19 +1,297 @@
 use crate::configuration::Configuration;
-use std::io::{self, ErrorKind};
+use input_check::{Validator, Violation};
+use std::io::{self, BufRead};
 
 use self::almanac::Almanac;
 
 mod almanac;
 
+/// Map headers, in the order the almanac chains them.
+pub const MAPS: [&str; 7] = [
+    "seed-to-soil map:",
+    "soil-to-fertilizer map:",
+    "fertilizer-to-water map:",
+    "water-to-light map:",
+    "light-to-temperature map:",
+    "temperature-to-humidity map:",
+    "humidity-to-location map:",
+];
+
 pub fn run(config: Configuration) -> io::Result<String> {
     if let Some(input_buf) = config.input_file_buffer {
-        let almanac = Almanac::new_from_buf(input_buf)?;
+        let lines = input_buf.lines().collect::<io::Result<Vec<_>>>()?;
+        invariants().validate(&lines).map_err(io::Error::other)?;
+
+        let almanac = Almanac::new_from_buf(lines.join("\n").as_bytes())?;
 
         let part1 = almanac.get_lowest_location();
         let part2 = almanac.get_lowest_location_with_seed_range();
 
         Ok(format!("part1: {}, part2: {}", part1, part2))
     } else {
-        Err(io::Error::new(ErrorKind::Other, "Input file required"))
+        Err(io::Error::other("Input file required"))
+    }
+}
+
+fn seeds(lines: &[String]) -> Option<Vec<&str>> {
+    lines
+        .first()?
+        .strip_prefix("seeds:")
+        .map(|seeds| seeds.split_whitespace().collect())
+}
+
+/// Part 2 reads the seeds as `start length` pairs, and every map range is
+/// `destination source length` with a non-empty length.
+fn invariants() -> Validator<'static> {
+    Validator::new()
+        .invariant("seeds line", |lines| match seeds(lines) {
+            None => Err(Violation::at(1, "expected `seeds: <numbers>`")),
+            Some(seeds) => match seeds.iter().find(|seed| seed.parse::<u64>().is_err()) {
+                Some(seed) => Err(Violation::at(1, format!("{:?} isn't a seed number", seed))),
+                None if seeds.is_empty() => Err(Violation::at(1, "no seeds")),
+                None => Ok(()),
+            },
+        })
+        .invariant("seeds come in start/length pairs", |lines| {
+            match seeds(lines).map(|seeds| seeds.len()) {
+                Some(n) if n % 2 == 1 => Err(Violation::at(
+                    1,
+                    format!("{} seed numbers, expected an even amount", n),
+                )),
+                _ => Ok(()),
+            }
+        })
+        .invariant("every map, in order", |lines| {
+            let mut expected = MAPS.iter();
+            for (ix, line) in lines.iter().enumerate() {
+                if line.ends_with("map:") {
+                    match expected.next() {
+                        Some(header) if header == line => {}
+                        Some(header) => {
+                            return Err(Violation::at(ix + 1, format!("expected {:?}", header)))
+                        }
+                        None => return Err(Violation::at(ix + 1, "unexpected map")),
+                    }
+                }
+            }
+            match expected.next() {
+                Some(header) => Err(Violation::new(format!("{:?} is missing", header))),
+                None => Ok(()),
+            }
+        })
+        .every_line("map ranges", 1, |line| {
+            if line.is_empty() || line.ends_with("map:") {
+                return Ok(());
+            }
+            let numbers = line
+                .split_whitespace()
+                .map(|n| n.parse::<u64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| format!("{:?} isn't `destination source length`", line))?;
+            match numbers[..] {
+                [_, _, 0] => Err("empty range".to_string()),
+                [destination, source, length]
+                    if destination.checked_add(length).is_none()
+                        || source.checked_add(length).is_none() =>
+                {
+                    Err("range overflows".to_string())
+                }
+                [_, _, _] => Ok(()),
+                _ => Err(format!("{:?} isn't `destination source length`", line)),
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use input_gen::differential::{smaller, without_blocks, Differential};
+
+    use crate::generator::generate;
+    use crate::solution::{almanac::Almanac, invariants, MAPS};
+
+    /// An almanac as numbers, so it can be shrunk: seed `(start, length)`
+    /// pairs and `(destination, source, length)` ranges per map.
+    #[derive(Clone)]
+    struct Case {
+        seeds: Vec<(u64, u64)>,
+        maps: Vec<Vec<(u64, u64, u64)>>,
+    }
+
+    impl Case {
+        fn parse(input: &str) -> Self {
+            let numbers = |line: &str| {
+                line.split_whitespace()
+                    .filter_map(|n| n.parse::<u64>().ok())
+                    .collect::<Vec<_>>()
+            };
+            let mut lines = input.lines();
+            let seeds = numbers(lines.next().unwrap())
+                .chunks(2)
+                .map(|pair| (pair[0], pair[1]))
+                .collect();
+            let mut maps = vec![];
+            for line in lines.filter(|line| !line.is_empty()) {
+                match numbers(line)[..] {
+                    [destination, source, length] => {
+                        let map: &mut Vec<_> = maps.last_mut().unwrap();
+                        map.push((destination, source, length));
+                    }
+                    _ => maps.push(vec![]),
+                }
+            }
+
+            Case { seeds, maps }
+        }
+
+        fn almanac(&self) -> Almanac {
+            Almanac::new_from_buf(self.to_string().as_bytes()).unwrap()
+        }
+
+        /// Fewer seed pairs or map ranges, then smaller numbers. Lengths
+        /// stay positive and at least one seed pair is kept.
+        fn shrink(&self) -> Vec<Case> {
+            let mut candidates = vec![];
+            for seeds in without_blocks(&self.seeds) {
+                if !seeds.is_empty() {
+                    candidates.push(Case {
+                        seeds,
+                        ..self.clone()
+                    });
+                }
+            }
+            for (ix, map) in self.maps.iter().enumerate() {
+                for ranges in without_blocks(map) {
+                    let mut case = self.clone();
+                    case.maps[ix] = ranges;
+                    candidates.push(case);
+                }
+            }
+
+            for (ix, &(start, length)) in self.seeds.iter().enumerate() {
+                for start in smaller(start) {
+                    let mut case = self.clone();
+                    case.seeds[ix] = (start, length);
+                    candidates.push(case);
+                }
+                for length in smaller(length).into_iter().filter(|&length| length > 0) {
+                    let mut case = self.clone();
+                    case.seeds[ix] = (start, length);
+                    candidates.push(case);
+                }
+            }
+            for (map_ix, map) in self.maps.iter().enumerate() {
+                for (ix, &(destination, source, length)) in map.iter().enumerate() {
+                    let mut shrunk = vec![];
+                    shrunk.extend(
+                        smaller(destination)
+                            .into_iter()
+                            .map(|d| (d, source, length)),
+                    );
+                    shrunk.extend(
+                        smaller(source)
+                            .into_iter()
+                            .map(|s| (destination, s, length)),
+                    );
+                    shrunk.extend(
+                        smaller(length)
+                            .into_iter()
+                            .filter(|&length| length > 0)
+                            .map(|l| (destination, source, l)),
+                    );
+                    for range in shrunk {
+                        let mut case = self.clone();
+                        case.maps[map_ix][ix] = range;
+                        candidates.push(case);
+                    }
+                }
+            }
+
+            candidates
+        }
+    }
+
+    impl fmt::Display for Case {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "seeds:")?;
+            for (start, length) in &self.seeds {
+                write!(f, " {} {}", start, length)?;
+            }
+            writeln!(f)?;
+            for (header, map) in MAPS.iter().zip(&self.maps) {
+                write!(f, "\n{}\n", header)?;
+                for (destination, source, length) in map {
+                    writeln!(f, "{} {} {}", destination, source, length)?;
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn example() {
+        let almanac = Almanac::new_from_buf(include_str!("../test").as_bytes()).unwrap();
+        assert_eq!(almanac.get_lowest_location(), 35);
+        assert_eq!(almanac.get_lowest_location_with_seed_range(), 46);
+    }
+
+    #[test]
+    fn seed_ranges_match_seed_by_seed() {
+        let differential = Differential::new(
+            |seed| Case::parse(&generate(seed, 1 + seed as usize % 13)),
+            |case| case.almanac().get_lowest_location_with_seed_range(),
+            |case| {
+                let almanac = case.almanac();
+                case.seeds
+                    .iter()
+                    .flat_map(|&(start, length)| start..start + length)
+                    .map(|seed| almanac.location(seed))
+                    .min()
+                    .unwrap()
+            },
+        )
+        .shrink(Case::shrink);
+
+        if let Err(mismatch) = differential.run(0..200) {
+            panic!("{}", mismatch);
+        }
+    }
+
+    #[test]
+    fn reports_broken_invariants() {
+        let lines = include_str!("../test")
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(invariants().validate(&lines), Ok(()));
+
+        let mut broken = lines.clone();
+        broken[0] = "seeds: 79 14 55".to_string();
+        broken[4] = "52 50 0".to_string();
+        let report = invariants().validate(&broken).unwrap_err().to_string();
+        assert!(report.contains("seeds come in start/length pairs (line 1): 3 seed numbers"));
+        assert!(report.contains("map ranges (line 5): empty range"));
+
+        let missing_map = lines
+            .iter()
+            .filter(|line| !line.starts_with("water-to-light"))
+            .cloned()
+            .collect::<Vec<_>>();
+        let report = invariants().validate(&missing_map).unwrap_err().to_string();
+        assert!(report.contains("expected \"water-to-light map:\""));
+    }
+
+    #[test]
+    fn generated_almanacs() {
+        for (seed, size) in (0..20).zip([1, 2, 3, 5, 8, 13, 21, 34, 55, 89].into_iter().cycle()) {
+            let input = generate(seed, size);
+            let lines = input.lines().map(String::from).collect::<Vec<_>>();
+
+            assert_eq!(invariants().validate(&lines), Ok(()));
+            let almanac = Almanac::new_from_buf(input.as_bytes()).unwrap();
+            assert!(almanac.get_lowest_location() < size as u64 * 100);
+        }
     }
 }
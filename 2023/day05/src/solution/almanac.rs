@@ -1,6 +1,5 @@
 use std::{
-    fs::File,
-    io::{self, BufRead, BufReader},
+    io::{self, BufRead},
     ops::Range,
 };
 
@@ -47,7 +46,7 @@ impl Almanac {
         }
     }
 
-    pub fn new_from_buf(reader: BufReader<File>) -> io::Result<Self> {
+    pub fn new_from_buf(reader: impl BufRead) -> io::Result<Self> {
         let mut almanac = Almanac::new();
         let mut input_phase = InputPhase::Seeds;
 
@@ -78,19 +77,21 @@ impl Almanac {
         Ok(almanac)
     }
 
+    pub fn location(&self, seed: u64) -> u64 {
+        // Could be done with a vec of mappings instead
+        self.htl.get(
+            self.tth.get(
+                self.ltt
+                    .get(self.wtl.get(self.ftw.get(self.stf.get(self.sts.get(seed))))),
+            ),
+        )
+    }
+
     pub fn get_lowest_location(&self) -> u64 {
         let mut lowest = u64::MAX;
 
         for seed in &self.seeds {
-            // Could be done with a vec of mappings instead
-            let loc = self.htl.get(
-                self.tth.get(
-                    self.ltt.get(
-                        self.wtl
-                            .get(self.ftw.get(self.stf.get(self.sts.get(*seed)))),
-                    ),
-                ),
-            );
+            let loc = self.location(*seed);
             if loc < lowest {
                 lowest = loc;
             }
@@ -182,8 +183,8 @@ impl AlmanacMap {
         let src_start = map_fields[1].parse::<SrcStart>().unwrap();
         let length = map_fields[2].parse::<Length>().unwrap();
 
-        let src_range = src_start..src_start + length - 1;
-        let dst_range = dst_start..dst_start + length - 1;
+        let src_range = src_start..src_start + length;
+        let dst_range = dst_start..dst_start + length;
         self.maps.push((src_range.clone(), dst_start));
         self.range_maps.push((src_range, dst_range));
     }
@@ -202,9 +203,9 @@ impl AlmanacMap {
     pub fn get_ranges(&self, input_ranges: Vec<Range<u64>>) -> Vec<Range<u64>> {
         let mut out_ranges = vec![];
 
-        for (mapped_src_range, mapped_dst_range) in &self.range_maps {
+        for input_range in &input_ranges {
             let mut intersections = vec![];
-            for input_range in &input_ranges {
+            for (mapped_src_range, mapped_dst_range) in &self.range_maps {
                 if let Some(intersection) = intersect(mapped_src_range, input_range) {
                     let out_dst_start = 
                         mapped_dst_range.start + intersection.start - mapped_src_range.start;
@@ -216,7 +217,8 @@ impl AlmanacMap {
                     intersections.push(intersection);
                 }
             }
-            out_ranges.extend(intersection_differences(mapped_src_range, intersections));
+            // Whatever no map range covers keeps its number
+            out_ranges.extend(intersection_differences(input_range, intersections));
         }
 
 
@@ -228,7 +230,7 @@ fn intersect(a: &Range<u64>, b: &Range<u64>) -> Option<Range<u64>> {
     let start = u64::max(a.start, b.start);
     let end = u64::min(a.end, b.end);
 
-    if start <= end {
+    if start < end {
         Some(start..end)
     } else {
         None
@@ -244,7 +246,7 @@ fn intersection_differences(range: &Range<u64>, mut intersections: Vec<Range<u64
 
     for intersection in &intersections {
         if intersection.start > range_cursor {
-            out_ranges.push(range_cursor..intersection.start - 1);
+            out_ranges.push(range_cursor..intersection.start);
         }
 
         if intersection.end > range_cursor {
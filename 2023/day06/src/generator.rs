@@ -0,0 +1,54 @@
+use input_gen::Rng;
+
+/// Longest part 2 race time whose record still fits in a `usize`.
+const MAX_DIGITS: usize = 9;
+
+/// Races whose times have `size` digits in total (1 to 9), split over
+/// about half as many races: 8 gives four races of two digit times like
+/// the puzzle. Records can always be beaten, including the part 2 race
+/// read from the joined columns.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let digits = size.clamp(1, MAX_DIGITS);
+    let n_races = digits.div_ceil(2);
+
+    loop {
+        let races = (0..n_races)
+            .map(|ix| {
+                // Earlier races get the odd digit
+                let race_digits = digits / n_races + usize::from(ix < digits % n_races);
+                let low = 10u64.pow(race_digits as u32 - 1).max(2);
+                let time = rng.between(low..=10u64.pow(race_digits as u32) - 1);
+                let best = (time / 2) * (time - time / 2);
+                (time, rng.below(best))
+            })
+            .collect::<Vec<_>>();
+
+        let joined = |column: fn(&(u64, u64)) -> u64| {
+            races
+                .iter()
+                .map(|race| column(race).to_string())
+                .collect::<String>()
+                .parse::<u64>()
+                .unwrap()
+        };
+        let (time, record) = (joined(|race| race.0), joined(|race| race.1));
+        if (time / 2) * (time - time / 2) <= record {
+            continue;
+        }
+
+        // A record has up to twice the digits of its time
+        let width = 1 + 2 * digits.div_ceil(n_races);
+        let column = |values: Vec<u64>| {
+            values
+                .iter()
+                .map(|value| format!("{:>width$}", value, width = width))
+                .collect::<String>()
+        };
+        return format!(
+            "Time:    {}\nDistance:{}\n",
+            column(races.iter().map(|race| race.0).collect()),
+            column(races.iter().map(|race| race.1).collect())
+        );
+    }
+}
@@ -1,23 +1,21 @@
 use crate::configuration::Configuration;
-use std::io;
-
-pub fn run(_config: Configuration) -> io::Result<String> {
-    let _test_races = vec![
-        RaceData { ms: 7, mm: 9 },
-        RaceData { ms: 15, mm: 40 },
-        RaceData { ms: 30, mm: 200 },
-    ];
-
-    let part_1_races = vec![
-        RaceData { ms: 46, mm: 208 },
-        RaceData { ms: 85, mm: 1412 },
-        RaceData { ms: 75, mm: 1257 },
-        RaceData { ms: 82, mm: 1410 },
-    ];
-
-    let part_2_race = RaceData {
-        ms: 46857582,
-        mm: 208141212571410,
+use std::io::{self, BufRead};
+
+pub fn run(config: Configuration) -> io::Result<String> {
+    let (part_1_races, part_2_race) = match config.input_file_buffer {
+        Some(input_buf) => parse_races(input_buf)?,
+        None => (
+            vec![
+                RaceData { ms: 46, mm: 208 },
+                RaceData { ms: 85, mm: 1412 },
+                RaceData { ms: 75, mm: 1257 },
+                RaceData { ms: 82, mm: 1410 },
+            ],
+            RaceData {
+                ms: 46857582,
+                mm: 208141212571410,
+            },
+        ),
     };
 
     let mut margin_of_error = 1usize;
@@ -34,11 +32,57 @@ pub fn run(_config: Configuration) -> io::Result<String> {
     ))
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct RaceData {
     ms: usize,
     mm: usize,
 }
 
+/// Reads the `Time:` and `Distance:` lines. Part 2 reads each line as a
+/// single number, ignoring the spaces between the columns.
+fn parse_races(reader: impl BufRead) -> io::Result<(Vec<RaceData>, RaceData)> {
+    let lines = reader.lines().collect::<io::Result<Vec<_>>>()?;
+    let values = |prefix: &str| {
+        let line = lines
+            .iter()
+            .find_map(|line| line.strip_prefix(prefix))
+            .ok_or_else(|| io::Error::other(format!("Missing {:?} line", prefix)))?;
+        let columns = line
+            .split_whitespace()
+            .map(|n| n.parse::<usize>().map_err(io::Error::other))
+            .collect::<io::Result<Vec<_>>>()?;
+        let joined = line
+            .replace(' ', "")
+            .parse::<usize>()
+            .map_err(io::Error::other)?;
+        Ok::<_, io::Error>((columns, joined))
+    };
+
+    let (times, time) = values("Time:")?;
+    let (distances, distance) = values("Distance:")?;
+    if times.len() != distances.len() {
+        return Err(io::Error::other(format!(
+            "{} times for {} distances",
+            times.len(),
+            distances.len()
+        )));
+    }
+
+    let races = times
+        .into_iter()
+        .zip(distances)
+        .map(|(ms, mm)| RaceData { ms, mm })
+        .collect();
+
+    Ok((
+        races,
+        RaceData {
+            ms: time,
+            mm: distance,
+        },
+    ))
+}
+
 fn get_count(race: &RaceData) -> usize {
     let mut button_time = 0usize;
     let count = loop {
@@ -52,3 +96,78 @@ fn get_count(race: &RaceData) -> usize {
 
     count
 }
+
+#[cfg(test)]
+mod tests {
+    use input_gen::differential::{smaller, Differential};
+
+    use crate::generator::generate;
+    use crate::solution::{get_count, parse_races, RaceData};
+
+    #[test]
+    fn example() {
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let (races, race) = parse_races(input.as_bytes()).unwrap();
+
+        assert_eq!(races[2], RaceData { ms: 30, mm: 200 });
+        assert_eq!(races.iter().map(get_count).product::<usize>(), 288);
+        assert_eq!(get_count(&race), 71503);
+    }
+
+    #[test]
+    fn rejects_mismatched_columns() {
+        let input = "Time:      7  15   30\nDistance:  9  40\n";
+        let error = parse_races(input.as_bytes()).unwrap_err();
+
+        assert_eq!(error.to_string(), "3 times for 2 distances");
+    }
+
+    #[test]
+    fn generated_races() {
+        for (seed, size) in (0..20).zip((1..=9).cycle()) {
+            let (races, race) = parse_races(generate(seed, size).as_bytes()).unwrap();
+
+            assert_eq!(races.len(), size.div_ceil(2));
+            assert_eq!(race.ms.to_string().len(), size);
+            assert!(races.iter().chain([&race]).all(|race| get_count(race) > 0));
+        }
+    }
+
+    fn ways_to_win(race: &RaceData) -> usize {
+        (0..=race.ms)
+            .filter(|button_time| button_time * (race.ms - button_time) > race.mm)
+            .count()
+    }
+
+    #[test]
+    fn count_matches_every_button_time() {
+        let differential = Differential::new(
+            |seed| {
+                parse_races(generate(seed, 1 + seed as usize % 6).as_bytes())
+                    .unwrap()
+                    .1
+            },
+            get_count,
+            ways_to_win,
+        )
+        .shrink(|race| {
+            let times = smaller(race.ms as u64).into_iter().map(|ms| RaceData {
+                ms: ms as usize,
+                ..race.clone()
+            });
+            let distances = smaller(race.mm as u64).into_iter().map(|mm| RaceData {
+                mm: mm as usize,
+                ..race.clone()
+            });
+            // get_count expects a race that can be won
+            times
+                .chain(distances)
+                .filter(|race| ways_to_win(race) > 0)
+                .collect()
+        });
+
+        if let Err(mismatch) = differential.run(0..200) {
+            panic!("{:?}", mismatch);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+const CARDS: &[u8] = b"AKQJT98765432";
+
+/// `size` distinct hands (at most 13^5) with bids between 1 and 1000. Each
+/// hand draws its cards from 1 to 5 card labels, so every hand type shows
+/// up, jokers included.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.min(CARDS.len().pow(5));
+    let mut seen = HashSet::new();
+    let mut input = String::new();
+
+    while seen.len() < size {
+        let mut labels = CARDS.to_vec();
+        rng.shuffle(&mut labels);
+        labels.truncate(rng.index(1..=5));
+
+        let hand = (0..5)
+            .map(|_| *rng.pick(&labels) as char)
+            .collect::<String>();
+        if seen.insert(hand.clone()) {
+            writeln!(input, "{} {}", hand, rng.between(1..=1000)).unwrap();
+        }
+    }
+
+    input
+}
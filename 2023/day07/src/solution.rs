@@ -233,3 +233,86 @@ impl FromStr for Hand {
         Ok(hand)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use crate::configuration::Configuration;
+    use crate::generator::generate;
+    use crate::solution::{run, CARD_VALUES, CARD_VALUES_WITH_JOKER};
+
+    /// Type of a hand as the sorted card counts, largest first.
+    fn naive_type(cards: &str) -> Vec<usize> {
+        let mut labels = cards.chars().collect::<Vec<_>>();
+        labels.sort_unstable();
+        labels.dedup();
+        let mut counts = labels
+            .iter()
+            .map(|&label| cards.matches(label).count())
+            .collect::<Vec<_>>();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        counts
+    }
+
+    /// Tries every card as the joker, then ranks the hands by type and cards.
+    fn naive_winnings(input: &str, j_as_joker: bool) -> usize {
+        let mut hands = input
+            .lines()
+            .map(|line| {
+                let (cards, bid) = line.split_once(' ').unwrap();
+                let best_type = if j_as_joker {
+                    "AKQT98765432"
+                        .chars()
+                        .map(|joker| naive_type(&cards.replace('J', &joker.to_string())))
+                        .max()
+                        .unwrap()
+                } else {
+                    naive_type(cards)
+                };
+                let values = if j_as_joker {
+                    &CARD_VALUES_WITH_JOKER
+                } else {
+                    &CARD_VALUES
+                };
+                let cards = cards.chars().map(|card| values[&card]).collect::<Vec<_>>();
+                (best_type, cards, bid.parse::<usize>().unwrap())
+            })
+            .collect::<Vec<_>>();
+        hands.sort();
+
+        hands
+            .iter()
+            .enumerate()
+            .map(|(ix, (_, _, bid))| bid * (ix + 1))
+            .sum()
+    }
+
+    fn run_on(path: &str) -> String {
+        run(Configuration::new(vec![String::new(), path.to_string()]).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn example() {
+        assert_eq!(run_on("test"), "part1: 6440, part2: 5905");
+    }
+
+    #[test]
+    fn generated_hands() {
+        for seed in 0..20 {
+            let input = generate(seed, 500);
+            let path = env::temp_dir().join(format!("day07-{}-{}", std::process::id(), seed));
+            fs::write(&path, &input).unwrap();
+
+            assert_eq!(
+                run_on(&path.display().to_string()),
+                format!(
+                    "part1: {}, part2: {}",
+                    naive_winnings(&input, false),
+                    naive_winnings(&input, true)
+                )
+            );
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
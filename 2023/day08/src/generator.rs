@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+
+use input_gen::Rng;
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+/// Last letters of the nodes that neither start nor end a walk.
+const PLAIN_ENDINGS: &[u8] = b"BCDEFGHIJKLMNOPQRSTUVWXY";
+const MAX_NODES: usize = 26 * 26 * 24;
+
+/// A network of about `size` nodes with the structure of the puzzle input:
+/// one to six ghosts walk a loop from their `A` node to their `Z` node,
+/// `AAA` to `ZZZ` being the first one, and from the `Z` node back to the
+/// start of the loop. Every loop is a multiple of the instruction length,
+/// so each node is always left through the same instruction and the other
+/// branch can lead anywhere.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let size = size.clamp(2, MAX_NODES);
+    let ghosts = rng.index(1..=6).min(size / 2);
+    let per_ghost = size / ghosts;
+    let length = rng.index(1..=((per_ghost as f64).sqrt() as usize).max(1));
+    let instructions = (0..length)
+        .map(|_| *rng.pick(b"LR") as char)
+        .collect::<String>();
+
+    let mut taken = HashSet::new();
+    let mut walks = vec![];
+    for ghost in 0..ghosts {
+        let repeats = rng.index(1..=(per_ghost / length).max(1));
+        let steps = repeats * length;
+        let (start, end) = if ghost == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (
+                name(&mut rng, b"A", &mut taken),
+                name(&mut rng, b"Z", &mut taken),
+            )
+        };
+        taken.extend([start.clone(), end.clone()]);
+
+        let mut walk = vec![start];
+        walk.extend((1..steps).map(|_| name(&mut rng, PLAIN_ENDINGS, &mut taken)));
+        walk.push(end);
+        walks.push(walk);
+    }
+
+    let every_node = walks.iter().flatten().cloned().collect::<Vec<_>>();
+    let mut definitions = vec![];
+    for walk in &walks {
+        let steps = walk.len() - 1;
+        for (t, node) in walk.iter().enumerate() {
+            // The Z node goes on like the A node
+            let next = if t == steps { &walk[1] } else { &walk[t + 1] };
+            let other = rng.pick(&every_node);
+            let (left, right) = if instructions.as_bytes()[t % length] == b'L' {
+                (next, other)
+            } else {
+                (other, next)
+            };
+            definitions.push(format!("{} = ({}, {})", node, left, right));
+        }
+    }
+    rng.shuffle(&mut definitions);
+
+    let mut input = format!("{}\n\n", instructions);
+    for definition in definitions {
+        writeln!(input, "{}", definition).unwrap();
+    }
+    input
+}
+
+/// An unused three letter name ending with one of `endings`.
+fn name(rng: &mut Rng, endings: &[u8], taken: &mut HashSet<String>) -> String {
+    loop {
+        let name = [*rng.pick(LETTERS), *rng.pick(LETTERS), *rng.pick(endings)];
+        let name = String::from_utf8(name.to_vec()).unwrap();
+        if taken.insert(name.clone()) {
+            return name;
+        }
+    }
+}
@@ -1,4 +1,5 @@
 mod configuration;
+mod generator;
 mod solution;
 use std::io::{self, stdin, Read};
 use std::{env, time::Instant};
@@ -9,6 +10,11 @@ fn main() -> io::Result<()> {
     let args: Vec<String> = env::args().collect::<Vec<String>>();
     let config = Configuration::new(args)?;
 
+    if let Some((seed, size)) = config.generate {
+        print!("{}", generator::generate(seed, size));
+        return Ok(());
+    }
+
     println!("Press enter key to start");
     stdin().read_exact(&mut [0u8]).unwrap();
 
@@ -1,16 +1,20 @@
 use crate::configuration::Configuration;
+use input_check::{Validator, Violation};
 use std::{
     cell::RefCell,
-    collections::HashMap,
-    io::{self, BufRead, ErrorKind},
+    collections::{HashMap, HashSet},
+    io::{self, BufRead},
     rc::Rc,
 };
 
 pub fn run(config: Configuration) -> io::Result<String> {
     if let Some(input_buf) = config.input_file_buffer {
-        let mut input_iterator = input_buf.lines();
+        let lines = input_buf.lines().collect::<io::Result<Vec<_>>>()?;
+        invariants().validate(&lines).map_err(io::Error::other)?;
 
-        let instruction = input_iterator.next().unwrap()?;
+        let mut input_iterator = lines.into_iter();
+
+        let instruction = input_iterator.next().unwrap();
         let mut nodes_map = HashMap::<String, Rc<RefCell<Node>>>::new();
 
         // skip empty line
@@ -18,9 +22,7 @@ pub fn run(config: Configuration) -> io::Result<String> {
 
         // create rest
         for line in input_iterator {
-            if let Ok(s) = line {
-                Node::new_from_string(s, &mut nodes_map);
-            }
+            Node::new_from_string(line, &mut nodes_map);
         }
 
         let root_node = nodes_map.get("AAA").unwrap().clone();
@@ -39,10 +41,76 @@ pub fn run(config: Configuration) -> io::Result<String> {
 
         Ok(format!("part1: {}, part2: {}", part_1, part_2))
     } else {
-        Err(io::Error::new(ErrorKind::Other, "Input file required"))
+        Err(io::Error::other("Input file required"))
     }
 }
 
+/// `NAME = (LEFT, RIGHT)`
+fn parse_node(line: &str) -> Option<(&str, &str, &str)> {
+    let (name, branches) = line.split_once(" = ")?;
+    let (left, right) = branches
+        .strip_prefix('(')?
+        .strip_suffix(')')?
+        .split_once(", ")?;
+    let is_name = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if is_name(name) && is_name(left) && is_name(right) {
+        Some((name, left, right))
+    } else {
+        None
+    }
+}
+
+fn defined_nodes(lines: &[String]) -> HashSet<&str> {
+    lines
+        .iter()
+        .skip(2)
+        .filter_map(|line| parse_node(line).map(|(name, _, _)| name))
+        .collect()
+}
+
+/// Part 1 walks from `AAA` to `ZZZ`, and every walk must stay on nodes the
+/// network defines.
+fn invariants() -> Validator<'static> {
+    Validator::new()
+        .invariant("L/R instructions", |lines| match lines.first() {
+            Some(line) if !line.is_empty() && line.chars().all(|c| c == 'L' || c == 'R') => Ok(()),
+            _ => Err(Violation::at(1, "expected a line of L and R")),
+        })
+        .invariant("blank line after the instructions", |lines| {
+            match lines.get(1).map(String::as_str) {
+                Some("") => Ok(()),
+                _ => Err(Violation::at(2, "expected an empty line")),
+            }
+        })
+        .every_line("node definitions", 2, |line| match parse_node(line) {
+            Some(_) => Ok(()),
+            None => Err(format!("{:?} isn't `NAME = (LEFT, RIGHT)`", line)),
+        })
+        .invariant("AAA and ZZZ nodes", |lines| {
+            let defined = defined_nodes(lines);
+            match ["AAA", "ZZZ"].iter().find(|name| !defined.contains(*name)) {
+                Some(name) => Err(Violation::new(format!("{} isn't defined", name))),
+                None => Ok(()),
+            }
+        })
+        .invariant("branches lead to defined nodes", |lines| {
+            let defined = defined_nodes(lines);
+            lines
+                .iter()
+                .enumerate()
+                .skip(2)
+                .filter_map(|(ix, line)| parse_node(line).map(|node| (ix, node)))
+                .find_map(|(ix, (_, left, right))| {
+                    [left, right]
+                        .into_iter()
+                        .find(|branch| !defined.contains(branch))
+                        .map(|branch| Violation::at(ix + 1, format!("{} isn't defined", branch)))
+                })
+                .map_or(Ok(()), Err)
+        })
+}
+
 fn simulate_instructions(
     root_node: &Rc<RefCell<Node>>,
     ending_in: &str,
@@ -167,3 +235,93 @@ impl PartialEq for Node {
         self.name == other.name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, env, fs};
+
+    use crate::configuration::Configuration;
+    use crate::generator::generate;
+    use crate::solution::{invariants, lcm, parse_node, run};
+
+    /// Steps from `start` to the first and the second node ending in `Z`.
+    fn steps_to_z(lines: &[String], start: &str) -> (usize, usize) {
+        let nodes = lines[2..]
+            .iter()
+            .map(|line| {
+                let (name, left, right) = parse_node(line).unwrap();
+                (name, (left, right))
+            })
+            .collect::<HashMap<_, _>>();
+        let mut instructions = lines[0].chars().cycle();
+        let mut node = start;
+        let mut reached = vec![];
+
+        for step in 1.. {
+            let (left, right) = nodes[node];
+            node = if instructions.next() == Some('L') {
+                left
+            } else {
+                right
+            };
+            if node.ends_with('Z') {
+                reached.push(step);
+                if reached.len() == 2 {
+                    break;
+                }
+            }
+        }
+
+        (reached[0], reached[1])
+    }
+
+    #[test]
+    fn reports_broken_invariants() {
+        let lines = include_str!("../test")
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>();
+        assert_eq!(invariants().validate(&lines), Ok(()));
+
+        let broken = ["LRX", "", "BBB = (AAA, CCC)", "AAA = (BBB BBB)"].map(String::from);
+        let report = invariants().validate(&broken).unwrap_err().to_string();
+        assert!(report.contains("L/R instructions (line 1)"));
+        assert!(report.contains("node definitions (line 4): \"AAA = (BBB BBB)\""));
+        assert!(report.contains("AAA and ZZZ nodes: AAA isn't defined"));
+        assert!(report.contains("branches lead to defined nodes (line 3): AAA isn't defined"));
+    }
+
+    #[test]
+    fn generated_networks() {
+        for (seed, size) in (0..20).zip((10..).step_by(50)) {
+            let input = generate(seed, size);
+            let lines = input.lines().map(String::from).collect::<Vec<_>>();
+            assert_eq!(invariants().validate(&lines), Ok(()));
+
+            // Ghosts loop back to their Z node in as many steps as it took
+            // to reach it, which is what the LCM of part 2 relies on
+            let starts = lines[2..]
+                .iter()
+                .filter_map(|line| parse_node(line).map(|(name, _, _)| name))
+                .filter(|name| name.ends_with('A'))
+                .collect::<Vec<_>>();
+            let mut part_2 = 1;
+            for start in &starts {
+                let (first, second) = steps_to_z(&lines, start);
+                assert_eq!(second, 2 * first);
+                part_2 = lcm(part_2, first);
+            }
+
+            let part_1 = steps_to_z(&lines, "AAA").0;
+            let path = env::temp_dir().join(format!("day08-{}-{}", std::process::id(), seed));
+            fs::write(&path, &input).unwrap();
+            let config = Configuration::new(vec![String::new(), path.display().to_string()]);
+
+            assert_eq!(
+                run(config.unwrap()).unwrap(),
+                format!("part1: {}, part2: {}", part_1, part_2)
+            );
+            fs::remove_file(path).unwrap();
+        }
+    }
+}
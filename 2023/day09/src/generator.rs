@@ -0,0 +1,36 @@
+use input_gen::Rng;
+
+const READINGS: i64 = 21;
+
+/// `size` histories of 21 readings, each a polynomial of degree 0 to 6 with
+/// integer coefficients in the binomial basis, so every reading and the
+/// extrapolated values are integers.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = Rng::new(seed);
+    let mut input = String::new();
+
+    for _ in 0..size {
+        let coefficients = (0..=rng.index(0..=6))
+            .map(|_| rng.between(0..=20) as i64 - 10)
+            .collect::<Vec<_>>();
+        let readings = (0..READINGS)
+            .map(|x| {
+                coefficients
+                    .iter()
+                    .enumerate()
+                    .map(|(k, c)| c * binomial(x, k as i64))
+                    .sum::<i64>()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        input.push_str(&readings.join(" "));
+        input.push('\n');
+    }
+
+    input
+}
+
+fn binomial(n: i64, k: i64) -> i64 {
+    (0..k).fold(1, |acc, i| acc * (n - i) / (i + 1))
+}
@@ -83,7 +83,8 @@ fn all_items_zero(items: &Vec<isize>) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use crate::solution::extrapolate;
+    use crate::generator::generate;
+    use crate::solution::{extrapolate, ExtrDir};
 
     #[test]
     fn history_1_fwd() {
@@ -124,4 +125,23 @@ mod tests {
             5isize
         );
     }
+
+    #[test]
+    fn generated_histories() {
+        for seed in 0..20 {
+            for line in generate(seed, 200).lines() {
+                let mut values = line
+                    .split(' ')
+                    .map(|value| value.parse::<isize>().unwrap())
+                    .collect::<Vec<_>>();
+
+                // Low degree polynomials: each end follows from the others
+                let last = values.pop().unwrap();
+                assert_eq!(extrapolate(&values, ExtrDir::Forwards), last);
+                values.push(last);
+                let first = values.remove(0);
+                assert_eq!(extrapolate(&values, ExtrDir::Backwards), first);
+            }
+        }
+    }
 }
@@ -11,3 +11,44 @@ pub fn run(config: Configuration) -> io::Result<&'static str> {
         Err(io::Error::new(ErrorKind::Other, "Input file required"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc::submit::extract_answer;
+
+    use crate::{configuration::Configuration, solution::run};
+
+    /// Runs the examples written by `aoc examples` and checks the answer each
+    /// part reports matches the expected one. Passes until examples are extracted.
+    #[test]
+    fn examples() {
+        let answers = fs::read_to_string("answers.txt").unwrap_or_default();
+
+        for (number, line) in answers.lines().enumerate() {
+            let (part, file, answer) = match line.split('\t').collect::<Vec<_>>()[..] {
+                [part, file, answer] => (part, file, answer),
+                _ => panic!(
+                    "answers.txt line {}: expected tab-separated part, file and answer, got {:?}",
+                    number + 1,
+                    line
+                ),
+            };
+            let part = part.parse::<u32>().unwrap_or_else(|_| {
+                panic!("answers.txt line {}: invalid part {:?}", number + 1, part)
+            });
+
+            let config = Configuration::new(vec![String::new(), file.to_string()]).unwrap();
+            let result = run(config).unwrap();
+            assert_eq!(
+                extract_answer(result, part).as_deref(),
+                Some(answer),
+                "Part {} on {} in {:?}",
+                part,
+                file,
+                result
+            );
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use crate::{configuration::Puzzle, ledger::Verdict};
+
+const USER_AGENT: &str = "advent-of-code-solutions submit tool";
+
+/// Posts `answer` to `<endpoint>/<year>/day/<day>/answer`, as the puzzle
+/// page's form does, and reads the verdict from the returned page.
+pub fn submit(
+    endpoint: &str,
+    session: &str,
+    puzzle: Puzzle,
+    answer: &str,
+) -> Result<Verdict, String> {
+    let url = format!(
+        "{}/{}/day/{}/answer",
+        endpoint.trim_end_matches('/'),
+        puzzle.year,
+        puzzle.day
+    );
+
+    let response = ureq::post(&url)
+        .timeout(Duration::from_secs(30))
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &puzzle.part.to_string()), ("answer", answer)])
+        .map_err(|e| format!("Couldn't submit to {}: {}", url, e))?;
+    let page = response
+        .into_string()
+        .map_err(|e| format!("Couldn't read the response from {}: {}", url, e))?;
+
+    parse_response(&page)
+}
+
+/// Reads the verdict from the `<article>` of an answer page.
+pub fn parse_response(page: &str) -> Result<Verdict, String> {
+    let article = page
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once("</article>"))
+        .map_or(page, |(article, _)| article);
+
+    if article.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if article.contains("That's not the right answer") {
+        if article.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if article.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if article.contains("You gave an answer too recently") {
+        Ok(Verdict::Wait(parse_wait(article).unwrap_or(60)))
+    } else if article.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err("Couldn't find a verdict in the response".to_string())
+    }
+}
+
+/// Seconds in `You have 1m 5s left to wait`.
+fn parse_wait(article: &str) -> Option<u64> {
+    let (before, _) = article.split_once(" left to wait")?;
+    let (_, amount) = before.rsplit_once("You have ")?;
+
+    amount.split_whitespace().try_fold(0, |seconds, part| {
+        let (value, unit) = [("h", 3600), ("m", 60), ("s", 1)]
+            .iter()
+            .find_map(|&(suffix, unit)| part.strip_suffix(suffix).map(|value| (value, unit)))?;
+        Some(seconds + value.parse::<u64>().ok()? * unit)
+    })
+}
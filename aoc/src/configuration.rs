@@ -0,0 +1,32 @@
+use std::path::PathBuf;
+
+pub const DEFAULT_ENDPOINT: &str = "https://adventofcode.com";
+pub const DEFAULT_LEDGER: &str = ".aoc-ledger";
+
+/// Which puzzle answer is meant. Parts are 1 or 2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub part: u32,
+}
+
+pub struct SubmitOptions {
+    pub puzzle: Puzzle,
+    /// Read from the solution output on stdin when missing.
+    pub answer: Option<String>,
+    pub endpoint: String,
+    pub session: Option<String>,
+    pub ledger_path: PathBuf,
+}
+
+pub struct ExamplesOptions {
+    /// Puzzle description page saved from the browser.
+    pub page: PathBuf,
+    pub out_dir: PathBuf,
+}
+
+pub enum Command {
+    Submit(SubmitOptions),
+    Examples(ExamplesOptions),
+}
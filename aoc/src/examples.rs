@@ -0,0 +1,173 @@
+use std::fmt::Write;
+use std::fs;
+
+use crate::configuration::ExamplesOptions;
+
+/// Example input of one part of a puzzle, with the answer the description
+/// gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u32,
+    pub input: String,
+    pub answer: Option<String>,
+}
+
+pub fn run(options: ExamplesOptions) -> Result<String, String> {
+    let page = fs::read_to_string(&options.page)
+        .map_err(|e| format!("Couldn't read {}: {}", options.page.display(), e))?;
+    let examples = extract(&page);
+    if examples.is_empty() {
+        return Err("No puzzle description found in the page".to_string());
+    }
+
+    fs::create_dir_all(&options.out_dir)
+        .map_err(|e| format!("Couldn't create {}: {}", options.out_dir.display(), e))?;
+
+    // Parts sharing an input share the file
+    let mut inputs: Vec<&str> = vec![];
+    let mut answers = String::new();
+    for example in &examples {
+        let known = inputs.iter().position(|&input| input == example.input);
+        let ix = known.unwrap_or(inputs.len());
+        let file_name = format!("example{}.txt", ix + 1);
+        if known.is_none() {
+            inputs.push(&example.input);
+            let path = options.out_dir.join(&file_name);
+            fs::write(&path, &example.input)
+                .map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+        }
+
+        if let Some(answer) = &example.answer {
+            writeln!(answers, "{}\t{}\t{}", example.part, file_name, answer).unwrap();
+        }
+    }
+
+    let path = options.out_dir.join("answers.txt");
+    fs::write(&path, &answers).map_err(|e| format!("Couldn't write {}: {}", path.display(), e))?;
+
+    Ok(format!(
+        "Wrote {} example input(s) and {} expected answer(s) to {}",
+        inputs.len(),
+        answers.lines().count(),
+        options.out_dir.display()
+    ))
+}
+
+/// One example per `<article>` of the description, that is per part. The
+/// example is the first `<pre><code>` block of the part (later ones are
+/// usually intermediate states), or the previous part's example when the
+/// part brings none. The answer is the last emphasised code in the part,
+/// which is where the description states the example's result.
+pub fn extract(page: &str) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+
+    for (ix, article) in sections(page, "<article", "</article>").enumerate() {
+        let input = sections(article, "<pre><code>", "</code></pre>")
+            .next()
+            .map(text)
+            .or_else(|| examples.last().map(|previous| previous.input.clone()));
+
+        let answer = ["<code><em>", "<em><code>"]
+            .iter()
+            .filter_map(|open| {
+                let start = article.rfind(open)? + open.len();
+                let end = article[start..].find("</")? + start;
+                Some((start, text(&article[start..end])))
+            })
+            .max_by_key(|&(start, _)| start)
+            .map(|(_, answer)| answer);
+
+        if let Some(input) = input {
+            examples.push(Example {
+                part: ix as u32 + 1,
+                input,
+                answer,
+            });
+        }
+    }
+
+    examples
+}
+
+/// Contents between every `open` (up to the end of its tag) and the
+/// following `close`.
+fn sections<'a>(html: &'a str, open: &'a str, close: &'a str) -> impl Iterator<Item = &'a str> {
+    let mut rest = html;
+
+    std::iter::from_fn(move || {
+        let start = rest.find(open)?;
+        let after_open = &rest[start + open.len()..];
+        let content_start = if open.ends_with('>') {
+            0
+        } else {
+            after_open.find('>')? + 1
+        };
+        let content = &after_open[content_start..];
+        let end = content.find(close)?;
+
+        rest = &content[end + close.len()..];
+        Some(&content[..end])
+    })
+}
+
+/// Drops tags and decodes the entities a puzzle page uses.
+fn text(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+
+    out.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::examples::{extract, Example};
+
+    const PAGE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+a&lt;b&gt;3 &amp; 4
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+<pre><code>intermediate</code></pre>
+</article>
+<p>Your puzzle answer was <code>54159</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>The <em>last</em> one is <em><code>281</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn extracts_examples_and_answers() {
+        let example = "1abc2\na<b>3 & 4\n".to_string();
+
+        assert_eq!(
+            extract(PAGE),
+            vec![
+                Example {
+                    part: 1,
+                    input: example.clone(),
+                    answer: Some("142".to_string()),
+                },
+                Example {
+                    part: 2,
+                    input: example,
+                    answer: Some("281".to_string()),
+                },
+            ]
+        );
+        assert!(extract("<html></html>").is_empty());
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::configuration::Puzzle;
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint about the direction.
+    Wrong,
+    /// Answered too recently, with the seconds left to wait.
+    Wait(u64),
+    /// The part was already solved, or isn't unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(seconds) => write!(f, "wait:{}", seconds),
+            Verdict::WrongLevel => write!(f, "wrong-level"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "too-high" => Ok(Verdict::TooHigh),
+            "too-low" => Ok(Verdict::TooLow),
+            "wrong" => Ok(Verdict::Wrong),
+            "wrong-level" => Ok(Verdict::WrongLevel),
+            _ => s
+                .strip_prefix("wait:")
+                .and_then(|seconds| seconds.parse::<u64>().ok())
+                .map(Verdict::Wait)
+                .ok_or_else(|| format!("Unknown verdict {:?}", s)),
+        }
+    }
+}
+
+/// A submission, `timestamp` being seconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub puzzle: Puzzle,
+    pub answer: String,
+    pub verdict: Verdict,
+    pub timestamp: u64,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.puzzle.year,
+            self.puzzle.day,
+            self.puzzle.part,
+            self.answer,
+            self.verdict,
+            self.timestamp
+        )
+    }
+
+    fn from_line(line: &str) -> Result<Self, String> {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        if fields.len() != 6 {
+            return Err(format!("Expected 6 tab separated fields in {:?}", line));
+        }
+        let number = |field: &str| {
+            field
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid number {:?}: {}", field, e))
+        };
+
+        Ok(Entry {
+            puzzle: Puzzle {
+                year: number(fields[0])? as u32,
+                day: number(fields[1])? as u32,
+                part: number(fields[2])? as u32,
+            },
+            answer: fields[3].to_string(),
+            verdict: fields[4].parse()?,
+            timestamp: number(fields[5])?,
+        })
+    }
+}
+
+/// Answers are a single number or word, without any whitespace.
+pub fn is_single_token(answer: &str) -> bool {
+    !answer.is_empty() && !answer.contains(char::is_whitespace)
+}
+
+/// Whether an answer is worth sending, according to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Submit,
+    AlreadyCorrect,
+    Refuse(String),
+}
+
+/// Every submission made so far, one tab separated line per submission.
+pub struct Ledger {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, which doesn't need to exist yet.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(format!("Couldn't read ledger {}: {}", path.display(), e)),
+        };
+
+        let entries = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(ix, line)| {
+                Entry::from_line(line)
+                    .map_err(|e| format!("{} line {}: {}", path.display(), ix + 1, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+        })
+    }
+
+    /// Appends `entry` to the ledger file.
+    pub fn record(&mut self, entry: Entry) -> Result<(), String> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| format!("Couldn't open ledger {}: {}", self.path.display(), e))?;
+        writeln!(file, "{}", entry.to_line())
+            .map_err(|e| format!("Couldn't write ledger {}: {}", self.path.display(), e))?;
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Refuses answers that aren't a single token, that were already
+    /// rejected, that fall outside the bounds given by earlier too high / too
+    /// low answers, or that would be sent while the server still asks to
+    /// wait. Waits apply to every puzzle, as the server enforces them per
+    /// account.
+    pub fn check(&self, puzzle: Puzzle, answer: &str, now: u64) -> Check {
+        if !is_single_token(answer) {
+            return Check::Refuse(format!("{:?} is not a single answer", answer));
+        }
+
+        let previous = self
+            .entries
+            .iter()
+            .filter(|entry| entry.puzzle == puzzle)
+            .collect::<Vec<_>>();
+
+        if let Some(correct) = previous.iter().find(|e| e.verdict == Verdict::Correct) {
+            return if correct.answer == answer {
+                Check::AlreadyCorrect
+            } else {
+                Check::Refuse(format!("Already solved with {}", correct.answer))
+            };
+        }
+
+        if let Some(rejected) = previous
+            .iter()
+            .find(|e| e.answer == answer && e.verdict.is_rejection())
+        {
+            return Check::Refuse(format!(
+                "{} was already rejected ({})",
+                answer, rejected.verdict
+            ));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |verdict: Verdict| {
+                previous
+                    .iter()
+                    .filter(move |e| e.verdict == verdict)
+                    .filter_map(|e| e.answer.parse::<i128>().ok())
+            };
+            if let Some(too_high) = bound(Verdict::TooHigh).min().filter(|&high| value >= high) {
+                return Check::Refuse(format!(
+                    "{} is not below {}, which was too high",
+                    value, too_high
+                ));
+            }
+            if let Some(too_low) = bound(Verdict::TooLow).max().filter(|&low| value <= low) {
+                return Check::Refuse(format!(
+                    "{} is not above {}, which was too low",
+                    value, too_low
+                ));
+            }
+        }
+
+        let wait_until = self
+            .entries
+            .iter()
+            .filter_map(|e| match e.verdict {
+                Verdict::Wait(seconds) => Some(e.timestamp + seconds),
+                _ => None,
+            })
+            .max();
+        if let Some(until) = wait_until.filter(|&until| until > now) {
+            return Check::Refuse(format!("Wait {}s before submitting again", until - now));
+        }
+
+        Check::Submit
+    }
+}
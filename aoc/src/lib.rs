@@ -0,0 +1,5 @@
+pub mod client;
+pub mod configuration;
+pub mod examples;
+pub mod ledger;
+pub mod submit;
@@ -0,0 +1,108 @@
+use std::env;
+use std::path::PathBuf;
+
+use aoc::configuration::{
+    Command, ExamplesOptions, Puzzle, SubmitOptions, DEFAULT_ENDPOINT, DEFAULT_LEDGER,
+};
+use aoc::{examples, submit};
+
+fn main() {
+    let args: Vec<String> = env::args().collect::<Vec<String>>();
+
+    let result = match configure(args) {
+        Command::Submit(options) => submit::run(options),
+        Command::Examples(options) => examples::run(options),
+    };
+
+    match result {
+        Ok(r) => println!("{}", r),
+        Err(e) => {
+            println!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn configure(args: Vec<String>) -> Command {
+    let mut args = args.into_iter().skip(1);
+
+    match args.next().as_deref() {
+        Some("submit") => Command::Submit(configure_submit(args)),
+        Some("examples") => Command::Examples(configure_examples(args)),
+        _ => panic!("Expected a command: submit or examples"),
+    }
+}
+
+/// Usage: `aoc submit --year Y --day D --part 1 | 2 [--answer A] [--endpoint URL]
+/// [--ledger <path>]`. Without `--answer`, the answer is taken from a
+/// solution's `Result:` output piped on stdin. The session cookie comes from
+/// the `AOC_SESSION` environment variable, the endpoint defaults to
+/// `AOC_ENDPOINT` or the Advent of Code website.
+fn configure_submit(mut args: impl Iterator<Item = String>) -> SubmitOptions {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut options = SubmitOptions {
+        puzzle: Puzzle {
+            year: 0,
+            day: 0,
+            part: 0,
+        },
+        answer: None,
+        endpoint: env::var("AOC_ENDPOINT").unwrap_or_else(|_| DEFAULT_ENDPOINT.to_string()),
+        session: env::var("AOC_SESSION").ok(),
+        ledger_path: PathBuf::from(DEFAULT_LEDGER),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--year" => year = args.next().and_then(|y| y.parse::<u32>().ok()),
+            "--day" => day = args.next().and_then(|d| d.parse::<u32>().ok()),
+            "--part" => part = args.next().and_then(|p| p.parse::<u32>().ok()),
+            "--answer" => match args.next() {
+                Some(answer) => options.answer = Some(answer),
+                None => panic!("Expected an answer after --answer"),
+            },
+            "--endpoint" => match args.next() {
+                Some(endpoint) => options.endpoint = endpoint,
+                None => panic!("Expected a URL after --endpoint"),
+            },
+            "--ledger" => match args.next() {
+                Some(path) => options.ledger_path = PathBuf::from(path),
+                None => panic!("Expected a path after --ledger"),
+            },
+            _ => panic!("Unknown argument {:?}", arg),
+        }
+    }
+
+    options.puzzle = match (year, day, part) {
+        (Some(year), Some(day @ 1..=25), Some(part @ 1..=2)) => Puzzle { year, day, part },
+        _ => panic!("Expected --year, --day (1 to 25) and --part (1 or 2)"),
+    };
+
+    options
+}
+
+/// Usage: `aoc examples <page.html> [--out <dir>]`. Writes the examples of a
+/// saved puzzle description as `example<N>.txt`, and the answers they should
+/// give as `answers.txt` (`<part>\t<example file>\t<answer>` lines), in the
+/// current directory by default.
+fn configure_examples(mut args: impl Iterator<Item = String>) -> ExamplesOptions {
+    let mut page = None;
+    let mut out_dir = PathBuf::from(".");
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => match args.next() {
+                Some(dir) => out_dir = PathBuf::from(dir),
+                None => panic!("Expected a directory after --out"),
+            },
+            _ => page = Some(PathBuf::from(arg)),
+        }
+    }
+
+    ExamplesOptions {
+        page: page.expect("Expected the path of a saved puzzle page"),
+        out_dir,
+    }
+}
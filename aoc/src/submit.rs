@@ -0,0 +1,249 @@
+use std::io::{self, Read};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{
+    client,
+    configuration::SubmitOptions,
+    ledger::{is_single_token, Check, Entry, Ledger, Verdict},
+};
+
+pub fn run(options: SubmitOptions) -> Result<String, String> {
+    let answer = match options.answer {
+        Some(answer) => answer,
+        None => {
+            let mut output = String::new();
+            io::stdin()
+                .read_to_string(&mut output)
+                .map_err(|e| format!("Couldn't read stdin: {}", e))?;
+            extract_answer(&output, options.puzzle.part)
+                .ok_or_else(|| format!("No answer for part {} on stdin", options.puzzle.part))?
+        }
+    };
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| e.to_string())?
+        .as_secs();
+
+    let mut ledger = Ledger::load(&options.ledger_path)?;
+    match ledger.check(options.puzzle, &answer, now) {
+        Check::Submit => {}
+        Check::AlreadyCorrect => return Ok(format!("{} is already known to be correct", answer)),
+        Check::Refuse(reason) => return Err(format!("Not submitting {}: {}", answer, reason)),
+    }
+
+    let session = options
+        .session
+        .ok_or("Set AOC_SESSION to the session cookie to submit")?;
+    let verdict = client::submit(&options.endpoint, &session, options.puzzle, &answer)?;
+    ledger.record(Entry {
+        puzzle: options.puzzle,
+        answer: answer.clone(),
+        verdict,
+        timestamp: now,
+    })?;
+
+    Ok(match verdict {
+        Verdict::Correct => format!("{} is correct!", answer),
+        Verdict::TooHigh => format!("{} is too high", answer),
+        Verdict::TooLow => format!("{} is too low", answer),
+        Verdict::Wrong => format!("{} is wrong", answer),
+        Verdict::Wait(seconds) => format!("Answered too recently, wait {}s", seconds),
+        Verdict::WrongLevel => "That part is already solved or still locked".to_string(),
+    })
+}
+
+/// The answer to `part` in a solution's output: the value after `Part N:`
+/// or `partN:`, in any case (up to a comma or the end of the line), or the
+/// whole `Result:` line for solutions printing a single answer. Anything but
+/// a single token isn't an answer.
+pub fn extract_answer(output: &str, part: u32) -> Option<String> {
+    let lowercase = output.to_ascii_lowercase();
+    let labelled = [format!("part {}:", part), format!("part{}:", part)]
+        .iter()
+        .filter_map(|label| lowercase.find(label.as_str()).map(|ix| ix + label.len()))
+        .min();
+
+    let answer = match labelled {
+        Some(start) => output[start..].lines().next()?.split(',').next()?,
+        None => output
+            .lines()
+            .find_map(|line| line.strip_prefix("Result:"))
+            .or_else(|| output.lines().find(|line| !line.trim().is_empty()))?,
+    };
+
+    Some(answer.trim().to_string()).filter(|answer| is_single_token(answer))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
+    use std::thread::{self, JoinHandle};
+
+    use crate::{
+        client::parse_response,
+        configuration::{Puzzle, SubmitOptions},
+        ledger::Verdict,
+        submit::{extract_answer, run},
+    };
+
+    /// Answers a single request with `message` in the page's article, and
+    /// hands back the raw request.
+    fn stub_server(message: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            let page = format!(
+                "<html><main><article><p>{}</p></article></main></html>",
+                message
+            );
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )
+            .unwrap();
+            request
+        });
+
+        (endpoint, handle)
+    }
+
+    fn temp_ledger(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("aoc-ledger-{}-{}", std::process::id(), name));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn options(endpoint: &str, ledger_path: &Path, day: u32, answer: &str) -> SubmitOptions {
+        SubmitOptions {
+            puzzle: Puzzle {
+                year: 2022,
+                day,
+                part: 1,
+            },
+            answer: Some(answer.to_string()),
+            endpoint: endpoint.to_string(),
+            session: Some("secret".to_string()),
+            ledger_path: ledger_path.to_path_buf(),
+        }
+    }
+
+    #[test]
+    fn refuses_answers_known_to_be_wrong() {
+        let ledger = temp_ledger("wrong");
+        // Nothing listens there, so any refused answer must not reach the network
+        let offline = "http://127.0.0.1:9";
+
+        assert!(run(options(offline, &ledger, 9, "part1: 500, part2: 7"))
+            .unwrap_err()
+            .contains("not a single answer"));
+
+        let (endpoint, server) =
+            stub_server("That's not the right answer; your answer is too high.");
+        assert_eq!(
+            run(options(&endpoint, &ledger, 9, "500")),
+            Ok("500 is too high".to_string())
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2022/day/9/answer "));
+        assert!(request.contains("session=secret"));
+        assert!(request.ends_with("level=1&answer=500"));
+
+        assert!(run(options(offline, &ledger, 9, "500"))
+            .unwrap_err()
+            .contains("already rejected"));
+        assert!(run(options(offline, &ledger, 9, "600"))
+            .unwrap_err()
+            .contains("too high"));
+
+        let (endpoint, server) =
+            stub_server("That's the right answer! You are one gold star closer.");
+        assert_eq!(
+            run(options(&endpoint, &ledger, 9, "400")),
+            Ok("400 is correct!".to_string())
+        );
+        server.join().unwrap();
+
+        assert!(run(options(offline, &ledger, 9, "400"))
+            .unwrap()
+            .contains("already known"));
+        assert!(run(options(offline, &ledger, 9, "450"))
+            .unwrap_err()
+            .contains("Already solved"));
+
+        let contents = fs::read_to_string(&ledger).unwrap();
+        let entries = contents.lines().collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].starts_with("2022\t9\t1\t500\ttoo-high\t"));
+        assert!(entries[1].starts_with("2022\t9\t1\t400\tcorrect\t"));
+        fs::remove_file(ledger).unwrap();
+    }
+
+    #[test]
+    fn waits_apply_to_every_puzzle() {
+        let ledger = temp_ledger("wait");
+
+        let (endpoint, server) = stub_server(
+            "You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait.",
+        );
+        assert!(run(options(&endpoint, &ledger, 1, "3"))
+            .unwrap()
+            .contains("wait 65s"));
+        server.join().unwrap();
+
+        let refused = run(options("http://127.0.0.1:9", &ledger, 2, "4")).unwrap_err();
+        assert!(refused.contains("Wait"));
+        fs::remove_file(ledger).unwrap();
+    }
+
+    #[test]
+    fn reads_answers_and_verdicts() {
+        let output = "Result: Part 1: 13, Part 2: 1\n\nTotal: 285µs\n";
+        assert_eq!(extract_answer(output, 1), Some("13".to_string()));
+        assert_eq!(extract_answer(output, 2), Some("1".to_string()));
+        assert_eq!(
+            extract_answer("Result: 197400\n\nTotal: 2ms", 2),
+            Some("197400".to_string())
+        );
+        assert_eq!(extract_answer("", 1), None);
+
+        let output = "Result: part1: 424490994, part2: 15290096\n\nElapsed time: 2ms\n";
+        assert_eq!(extract_answer(output, 1), Some("424490994".to_string()));
+        assert_eq!(extract_answer(output, 2), Some("15290096".to_string()));
+        assert_eq!(
+            extract_answer("Result: PART 2: 7", 2),
+            Some("7".to_string())
+        );
+        assert_eq!(extract_answer(output, 3), None);
+        assert_eq!(extract_answer("Part 2: 24933642 (/d)\n", 2), None);
+
+        let page = "<article><p>That's not the right answer. If you're stuck...</p></article>";
+        assert_eq!(parse_response(page), Ok(Verdict::Wrong));
+        assert!(parse_response("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,161 @@
+//! Up-front checks of the input shape a solution relies on, so a malformed
+//! input is reported as a list of broken invariants instead of a panic deep
+//! inside the solution.
+
+use std::{error::Error, fmt};
+
+/// Where and how an invariant is broken. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Violation {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            message: message.into(),
+        }
+    }
+
+    pub fn at(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub invariant: &'static str,
+    pub violation: Violation,
+}
+
+/// Every invariant the input broke, in declaration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub failures: Vec<Failure>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Input doesn't have the expected shape:")?;
+        for failure in &self.failures {
+            write!(f, "\n  - {}", failure.invariant)?;
+            if let Some(line) = failure.violation.line {
+                write!(f, " (line {})", line)?;
+            }
+            write!(f, ": {}", failure.violation.message)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Report {}
+
+type Check<'a> = Box<dyn Fn(&[String]) -> Result<(), Violation> + 'a>;
+
+/// Named invariants over the lines of an input.
+#[derive(Default)]
+pub struct Validator<'a> {
+    invariants: Vec<(&'static str, Check<'a>)>,
+}
+
+impl<'a> Validator<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an invariant over the whole input.
+    pub fn invariant(
+        mut self,
+        name: &'static str,
+        check: impl Fn(&[String]) -> Result<(), Violation> + 'a,
+    ) -> Self {
+        self.invariants.push((name, Box::new(check)));
+        self
+    }
+
+    /// Adds an invariant every line after the first `skip` ones must hold.
+    /// The first failing line is reported.
+    pub fn every_line(
+        self,
+        name: &'static str,
+        skip: usize,
+        check: impl Fn(&str) -> Result<(), String> + 'a,
+    ) -> Self {
+        self.invariant(name, move |lines| {
+            lines
+                .iter()
+                .enumerate()
+                .skip(skip)
+                .try_for_each(|(ix, line)| check(line).map_err(|e| Violation::at(ix + 1, e)))
+        })
+    }
+
+    /// Checks every invariant, so a single run reports all the problems.
+    pub fn validate(&self, lines: &[String]) -> Result<(), Report> {
+        let failures = self
+            .invariants
+            .iter()
+            .filter_map(|(invariant, check)| {
+                check(lines).err().map(|violation| Failure {
+                    invariant,
+                    violation,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(Report { failures })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Validator, Violation};
+
+    fn lines(input: &str) -> Vec<String> {
+        input.lines().map(str::to_string).collect()
+    }
+
+    fn validator() -> Validator<'static> {
+        Validator::new()
+            .invariant("not empty", |lines| {
+                if lines.is_empty() {
+                    Err(Violation::new("no lines"))
+                } else {
+                    Ok(())
+                }
+            })
+            .every_line("numbers", 1, |line| {
+                line.parse::<u32>()
+                    .map(|_| ())
+                    .map_err(|_| format!("{:?} isn't a number", line))
+            })
+    }
+
+    #[test]
+    fn reports_every_broken_invariant() {
+        assert_eq!(validator().validate(&lines("header\n1\n2")), Ok(()));
+
+        let report = validator().validate(&lines("header\n1\nx\ny")).unwrap_err();
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(
+            report.failures[0].violation,
+            Violation::at(3, "\"x\" isn't a number")
+        );
+        assert_eq!(
+            report.to_string(),
+            "Input doesn't have the expected shape:\n  - numbers (line 3): \"x\" isn't a number"
+        );
+
+        let report = validator().validate(&[]).unwrap_err();
+        assert_eq!(report.failures[0].invariant, "not empty");
+    }
+}
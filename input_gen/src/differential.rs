@@ -0,0 +1,187 @@
+//! Differential testing: an optimised implementation and a reference one
+//! (usually brute force) run on generated inputs, and the first input they
+//! disagree on is shrunk to a minimal counterexample.
+
+use std::fmt;
+
+/// Most shrinking steps taken, in case a shrinker keeps offering inputs
+/// that are not actually smaller.
+const MAX_SHRINK_STEPS: usize = 10_000;
+
+/// An input the implementations disagree on, after shrinking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<I, O> {
+    /// Seed of the generated input the mismatch was found with.
+    pub seed: u64,
+    pub input: I,
+    pub fast: O,
+    pub reference: O,
+    /// Shrinking steps from the generated input to `input`.
+    pub steps: usize,
+}
+
+impl<I: fmt::Display, O: fmt::Debug> fmt::Display for Mismatch<I, O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Seed {} (shrunk {} times): fast gives {:?}, reference gives {:?} for",
+            self.seed, self.steps, self.fast, self.reference
+        )?;
+        write!(f, "{}", self.input)
+    }
+}
+
+type Generate<'a, I> = Box<dyn Fn(u64) -> I + 'a>;
+type Solve<'a, I, O> = Box<dyn Fn(&I) -> O + 'a>;
+type Shrink<'a, I> = Box<dyn Fn(&I) -> Vec<I> + 'a>;
+
+pub struct Differential<'a, I, O> {
+    generate: Generate<'a, I>,
+    fast: Solve<'a, I, O>,
+    reference: Solve<'a, I, O>,
+    shrink: Shrink<'a, I>,
+}
+
+impl<'a, I: Clone, O: PartialEq> Differential<'a, I, O> {
+    pub fn new(
+        generate: impl Fn(u64) -> I + 'a,
+        fast: impl Fn(&I) -> O + 'a,
+        reference: impl Fn(&I) -> O + 'a,
+    ) -> Self {
+        Self {
+            generate: Box::new(generate),
+            fast: Box::new(fast),
+            reference: Box::new(reference),
+            shrink: Box::new(|_| vec![]),
+        }
+    }
+
+    /// Candidates for shrinking, simplest first. Every candidate must be a
+    /// valid input; the first one still showing a mismatch replaces the
+    /// input until no candidate does.
+    pub fn shrink(mut self, shrink: impl Fn(&I) -> Vec<I> + 'a) -> Self {
+        self.shrink = Box::new(shrink);
+        self
+    }
+
+    /// Compares both implementations on the input of every seed, stopping
+    /// at the first mismatch.
+    pub fn run(&self, seeds: impl IntoIterator<Item = u64>) -> Result<(), Mismatch<I, O>> {
+        for seed in seeds {
+            let input = (self.generate)(seed);
+            if let Some((fast, reference)) = self.disagreement(&input) {
+                return Err(self.minimise(Mismatch {
+                    seed,
+                    input,
+                    fast,
+                    reference,
+                    steps: 0,
+                }));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn disagreement(&self, input: &I) -> Option<(O, O)> {
+        let fast = (self.fast)(input);
+        let reference = (self.reference)(input);
+        (fast != reference).then_some((fast, reference))
+    }
+
+    fn minimise(&self, mut mismatch: Mismatch<I, O>) -> Mismatch<I, O> {
+        while mismatch.steps < MAX_SHRINK_STEPS {
+            let smaller = (self.shrink)(&mismatch.input)
+                .into_iter()
+                .find_map(|input| Some((self.disagreement(&input)?, input)));
+            match smaller {
+                Some(((fast, reference), input)) => {
+                    mismatch = Mismatch {
+                        input,
+                        fast,
+                        reference,
+                        steps: mismatch.steps + 1,
+                        ..mismatch
+                    };
+                }
+                None => break,
+            }
+        }
+
+        mismatch
+    }
+}
+
+/// Copies of `items` with a block of items removed: the first and second
+/// half, then smaller blocks down to single items.
+pub fn without_blocks<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+    let mut block = items.len() / 2;
+
+    while block > 0 {
+        for start in (0..items.len()).step_by(block) {
+            let end = (start + block).min(items.len());
+            let mut candidate = items[..start].to_vec();
+            candidate.extend_from_slice(&items[end..]);
+            candidates.push(candidate);
+        }
+        block /= 2;
+    }
+    if items.len() == 1 {
+        candidates.push(vec![]);
+    }
+
+    candidates
+}
+
+/// Smaller values to try in place of `n`: 0, half of it and one less.
+pub fn smaller(n: u64) -> Vec<u64> {
+    let mut candidates = vec![0, n / 2, n.saturating_sub(1)];
+    candidates.dedup();
+    candidates.retain(|&candidate| candidate < n);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::differential::{smaller, without_blocks, Differential};
+
+    #[test]
+    fn shrinks_to_minimal_counterexample() {
+        // A "fast" sum that drops numbers above 100
+        let differential = Differential::new(
+            |seed| (0..20).map(|n| n * seed).collect::<Vec<u64>>(),
+            |numbers: &Vec<u64>| numbers.iter().filter(|&&n| n <= 100).sum::<u64>(),
+            |numbers: &Vec<u64>| numbers.iter().sum::<u64>(),
+        )
+        .shrink(|numbers| {
+            let mut candidates = without_blocks(numbers);
+            for (ix, &n) in numbers.iter().enumerate() {
+                candidates.extend(smaller(n).into_iter().map(|smaller| {
+                    let mut candidate = numbers.clone();
+                    candidate[ix] = smaller;
+                    candidate
+                }));
+            }
+            candidates
+        });
+
+        let mismatch = differential.run(0..10).unwrap_err();
+        assert_eq!(mismatch.seed, 6);
+        assert_eq!(mismatch.input, vec![101]);
+        assert_eq!((mismatch.fast, mismatch.reference), (0, 101));
+        assert!(differential.run(0..6).is_ok());
+    }
+
+    #[test]
+    fn shrinking_candidates() {
+        assert_eq!(
+            without_blocks(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert_eq!(without_blocks(&[1]), vec![Vec::<i32>::new()]);
+        assert_eq!(smaller(10), vec![0, 5, 9]);
+        assert_eq!(smaller(1), vec![0]);
+        assert!(smaller(0).is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Seeded randomness for the per-day input generators. The generator is
+//! SplitMix64, so the same seed gives the same input on every platform and
+//! toolchain. `differential` runs fast solvers against reference ones on
+//! generated inputs.
+
+use std::io::{self, ErrorKind};
+use std::ops::RangeInclusive;
+
+pub mod differential;
+
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0..n`. `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        assert!(n > 0, "Empty range");
+        // Multiply-shift keeps the bias negligible without a rejection loop
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn between(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (low, high) = range.into_inner();
+        assert!(low <= high, "Empty range");
+        match (high - low).checked_add(1) {
+            Some(span) => low + self.below(span),
+            None => self.next_u64(),
+        }
+    }
+
+    /// `between` for `usize`, the most common case in generators.
+    pub fn index(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (low, high) = range.into_inner();
+        self.between(low as u64..=high as u64) as usize
+    }
+
+    pub fn chance(&mut self, probability: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(0..=i));
+        }
+    }
+}
+
+/// Reads the `<seed> <size>` following a `--generate` flag.
+pub fn parse_args(args: &mut impl Iterator<Item = String>) -> Option<(u64, usize)> {
+    let seed = args.next()?.parse::<u64>().ok()?;
+    let size = args.next()?.parse::<usize>().ok()?;
+    Some((seed, size))
+}
+
+/// The seed and size of a `<binary> --generate <seed> <size>` command line,
+/// or `None` when the arguments don't start with `--generate`.
+pub fn parse_generate_flag(args: &[String]) -> io::Result<Option<(u64, usize)>> {
+    match args {
+        [_, flag, rest @ ..] if flag == "--generate" => parse_args(&mut rest.iter().cloned())
+            .filter(|_| rest.len() == 2)
+            .map(Some)
+            .ok_or_else(|| {
+                io::Error::new(
+                    ErrorKind::InvalidInput,
+                    "Expected a seed and a size after --generate",
+                )
+            }),
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{parse_generate_flag, Rng};
+
+    #[test]
+    fn seeded_and_in_range() {
+        let draws = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..100).map(|_| rng.between(3..=9)).collect::<Vec<_>>()
+        };
+
+        assert_eq!(draws(1), draws(1));
+        assert_ne!(draws(1), draws(2));
+        assert!(draws(3).iter().all(|n| (3..=9).contains(n)));
+        assert_eq!(Rng::new(0).between(5..=5), 5);
+        assert_eq!(Rng::new(0).between(0..=u64::MAX), Rng::new(0).next_u64());
+
+        let mut items = (0..20).collect::<Vec<_>>();
+        Rng::new(4).shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn reads_the_generate_flag() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            parse_generate_flag(&args(&["day05", "--generate", "7", "100"])).unwrap(),
+            Some((7, 100))
+        );
+        assert_eq!(
+            parse_generate_flag(&args(&["day05", "input"])).unwrap(),
+            None
+        );
+        assert_eq!(parse_generate_flag(&args(&["day05"])).unwrap(), None);
+        assert!(parse_generate_flag(&args(&["day05", "--generate", "7"])).is_err());
+        assert!(parse_generate_flag(&args(&["day05", "--generate", "x", "1"])).is_err());
+        assert!(parse_generate_flag(&args(&["day05", "--generate", "7", "1", "2"])).is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! The `--part 1 | 2 | both` selection shared by the days that compute both
+//! parts in one run.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn from_arg(arg: &str) -> Option<Self> {
+        match arg {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            "both" => Some(Part::Both),
+            _ => None,
+        }
+    }
+
+    /// Formats the answers of the selected part(s). Only the selected parts
+    /// are computed.
+    pub fn report(
+        self,
+        part_1: impl FnOnce() -> String,
+        part_2: impl FnOnce() -> String,
+    ) -> String {
+        match self {
+            Part::One => format!("Part 1: {}", part_1()),
+            Part::Two => format!("Part 2: {}", part_2()),
+            Part::Both => format!("Part 1: {}, Part 2: {}", part_1(), part_2()),
+        }
+    }
+
+    /// Same as `report` for computations that can fail.
+    pub fn try_report<E>(
+        self,
+        part_1: impl FnOnce() -> Result<String, E>,
+        part_2: impl FnOnce() -> Result<String, E>,
+    ) -> Result<String, E> {
+        Ok(match self {
+            Part::One => format!("Part 1: {}", part_1()?),
+            Part::Two => format!("Part 2: {}", part_2()?),
+            Part::Both => format!("Part 1: {}, Part 2: {}", part_1()?, part_2()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Part;
+
+    #[test]
+    fn reports_only_the_selected_parts() {
+        assert_eq!(Part::from_arg("2"), Some(Part::Two));
+        assert_eq!(Part::from_arg("3"), None);
+
+        let unreachable = || -> String { panic!("part 1 isn't selected") };
+        assert_eq!(
+            Part::Two.report(unreachable, || "42".to_string()),
+            "Part 2: 42"
+        );
+        assert_eq!(
+            Part::Both.try_report(|| Ok("1".to_string()), || Err("failed")),
+            Err("failed")
+        );
+    }
+}